@@ -19,8 +19,16 @@ impl Height {
     epoch_starting_sat + (self - epoch_starting_height.n()).n() * epoch.subsidy()
   }
 
+  pub(crate) fn period(self) -> u64 {
+    self.0 / DIFFCHANGE_INTERVAL
+  }
+
+  pub(crate) fn period_starting_height(self) -> Height {
+    Height(self.period() * DIFFCHANGE_INTERVAL)
+  }
+
   pub(crate) fn period_offset(self) -> u64 {
-    self.0 % DIFFCHANGE_INTERVAL
+    (self - self.period_starting_height().n()).n()
   }
 }
 
@@ -111,6 +119,28 @@ mod tests {
     );
   }
 
+  #[test]
+  fn period() {
+    assert_eq!(Height(0).period(), 0);
+    assert_eq!(Height(DIFFCHANGE_INTERVAL - 1).period(), 0);
+    assert_eq!(Height(DIFFCHANGE_INTERVAL).period(), 1);
+    assert_eq!(Height(DIFFCHANGE_INTERVAL * 2 + 1).period(), 2);
+  }
+
+  #[test]
+  fn period_starting_height() {
+    assert_eq!(Height(0).period_starting_height(), 0);
+    assert_eq!(Height(DIFFCHANGE_INTERVAL - 1).period_starting_height(), 0);
+    assert_eq!(
+      Height(DIFFCHANGE_INTERVAL).period_starting_height(),
+      DIFFCHANGE_INTERVAL
+    );
+    assert_eq!(
+      Height(DIFFCHANGE_INTERVAL * 2 + 1).period_starting_height(),
+      DIFFCHANGE_INTERVAL * 2
+    );
+  }
+
   #[test]
   fn period_offset() {
     assert_eq!(Height(0).period_offset(), 0);
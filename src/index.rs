@@ -1,9 +1,12 @@
 use {
   self::{
+    block_source::{BlockCache, LazyFileBlockSource},
     entry::{
-      BlockHashValue, Entry, InscriptionEntry, InscriptionEntryValue, InscriptionIdValue,
-      OutPointValue, RuneEntryValue, RuneIdValue, SatPointValue, SatRange,
+      BlockHashValue, Entry, HeaderValue, InscriptionEntry, InscriptionEntryValue,
+      InscriptionIdValue, OutPointValue, RuneEntryValue, RuneIdValue, SatPointHistoryValue,
+      SatPointValue, TxidValue,
     },
+    reloadable_client::ReloadableClient,
     reorg::*,
     runes::{Rune, RuneId},
     updater::Updater,
@@ -12,7 +15,7 @@ use {
   crate::subcommand::find::FindRangeOutput,
   crate::wallet::Wallet,
   bitcoin::block::Header,
-  bitcoincore_rpc::{json::GetBlockHeaderResult, Client},
+  bitcoincore_rpc::{json::GetBlockHeaderResult, Auth, Client},
   chrono::SubsecRound,
   indicatif::{ProgressBar, ProgressStyle},
   log::log_enabled,
@@ -22,12 +25,20 @@ use {
   },
   std::collections::HashMap,
   std::io::{BufWriter, Read, Write},
+  tokio::sync::broadcast,
 };
 
-pub(crate) use self::entry::RuneEntry;
+pub(crate) use self::{
+  entry::RuneEntry,
+  lot::{Lot, LotQueue},
+  updater::assign_sat_ranges,
+};
 
+mod block_source;
 mod entry;
 mod fetcher;
+mod lot;
+mod reloadable_client;
 mod reorg;
 mod rtx;
 mod updater;
@@ -35,7 +46,7 @@ mod updater;
 #[cfg(test)]
 pub(crate) mod testing;
 
-const SCHEMA_VERSION: u64 = 9;
+const SCHEMA_VERSION: u64 = 19;
 
 macro_rules! define_table {
   ($name:ident, $key:ty, $value:ty) => {
@@ -50,27 +61,48 @@ macro_rules! define_multimap_table {
   };
 }
 
+type Migration = fn(&WriteTransaction) -> Result;
+
+/// Migrations from each listed schema version to the next. Indexes older than
+/// the first version listed must be rebuilt.
+const MIGRATIONS: &[(u64, Migration)] = &[
+  (15, Index::migrate_sat_to_rarity),
+  (16, Index::migrate_content_hash_to_inscription_ids),
+  (17, Index::migrate_height_to_hash_checksum),
+  (18, Index::migrate_index_spent_outputs),
+];
+
+define_multimap_table! { CONTENT_HASH_TO_INSCRIPTION_IDS, &[u8; 32], &InscriptionIdValue }
 define_multimap_table! { INSCRIPTION_ID_TO_CHILDREN, &InscriptionIdValue, &InscriptionIdValue }
 define_multimap_table! { SATPOINT_TO_INSCRIPTION_ID, &SatPointValue, &InscriptionIdValue }
 define_multimap_table! { SAT_TO_INSCRIPTION_ID, u64, &InscriptionIdValue }
+define_multimap_table! { SAT_TO_SATPOINT_HISTORY, u64, &SatPointHistoryValue }
+define_multimap_table! { SCRIPT_PUBKEY_TO_OUTPOINT, &[u8], &OutPointValue }
 define_table! { HEIGHT_TO_BLOCK_HASH, u64, &BlockHashValue }
+define_table! { HEIGHT_TO_BLOCK_HEADER, u64, &HeaderValue }
+define_table! { HEIGHT_TO_HASH_CHECKSUM, u64, u32 }
 define_table! { HEIGHT_TO_LAST_SEQUENCE_NUMBER, u64, u64 }
 define_table! { INSCRIPTION_ID_TO_INSCRIPTION_ENTRY, &InscriptionIdValue, InscriptionEntryValue }
 define_table! { INSCRIPTION_ID_TO_SATPOINT, &InscriptionIdValue, &SatPointValue }
 define_table! { INSCRIPTION_NUMBER_TO_INSCRIPTION_ID, i64, &InscriptionIdValue }
 define_table! { OUTPOINT_TO_RUNE_BALANCES, &OutPointValue, &[u8] }
 define_table! { OUTPOINT_TO_SAT_RANGES, &OutPointValue, &[u8] }
+define_table! { OUTPOINT_TO_SCRIPT_PUBKEY, &OutPointValue, &[u8] }
 define_table! { OUTPOINT_TO_VALUE, &OutPointValue, u64}
 define_table! { RUNE_ID_TO_RUNE_ENTRY, RuneIdValue, RuneEntryValue }
 define_table! { RUNE_TO_RUNE_ID, u128, RuneIdValue }
+define_table! { SAT_TO_RARITY, u64, u8 }
 define_table! { SAT_TO_SATPOINT, u64, &SatPointValue }
 define_table! { SEQUENCE_NUMBER_TO_INSCRIPTION_ID, u64, &InscriptionIdValue }
+define_table! { SPENT_OUTPOINT_TO_HEIGHT, &OutPointValue, u64 }
 define_table! { STATISTIC_TO_COUNT, u64, u64 }
+define_table! { TXID_TO_BLOCK_HEIGHT, &TxidValue, u64 }
 define_table! { WRITE_TRANSACTION_STARTING_BLOCK_COUNT_TO_TIMESTAMP, u64, u128 }
 
 #[derive(Debug, PartialEq)]
 pub enum List {
   Spent,
+  Unconfirmed,
   Unspent(Vec<(u64, u64)>),
 }
 
@@ -79,13 +111,19 @@ pub(crate) enum Statistic {
   BlessedInscriptions,
   Commits,
   CursedInscriptions,
+  IndexAddresses,
   IndexRunes,
   IndexSats,
+  IndexTxids,
   LostSats,
+  OutputsCached,
   OutputsTraversed,
+  OutputsUncached,
   SatRanges,
   Schema,
   UnboundInscriptions,
+  // keys are positional, so new statistics go last
+  IndexSpentOutputs,
 }
 
 impl Statistic {
@@ -100,18 +138,62 @@ impl From<Statistic> for u64 {
   }
 }
 
+pub fn decode_sat_ranges(bytes: &[u8]) -> Result<Vec<(u64, u64)>> {
+  if bytes.len() % 11 != 0 {
+    bail!(
+      "sat range bytes must be a multiple of 11 bytes long, got {}",
+      bytes.len()
+    );
+  }
+
+  Ok(
+    bytes
+      .chunks_exact(11)
+      .map(|chunk| Lot::load(chunk.try_into().unwrap()).into())
+      .collect(),
+  )
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub(crate) struct BlockEvent {
+  pub(crate) block_hash: BlockHash,
+  pub(crate) height: u64,
+  pub(crate) inscriptions_added: u64,
+  pub(crate) ordinals_mined: u64,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub(crate) struct InscriptionEvent {
+  pub(crate) block_height: u64,
+  pub(crate) content_type: Option<String>,
+  pub(crate) inscription_id: InscriptionId,
+  pub(crate) sat: Option<Sat>,
+  pub(crate) txid: Txid,
+}
+
 #[derive(Serialize)]
 pub(crate) struct Info {
   pub(crate) blocks_indexed: u64,
   pub(crate) branch_pages: u64,
+  pub(crate) chain_blocks: u64,
+  pub(crate) commits: u64,
   pub(crate) fragmented_bytes: u64,
+  pub(crate) index_addresses: bool,
   pub(crate) index_file_size: u64,
   pub(crate) index_path: PathBuf,
+  pub(crate) index_runes: bool,
+  pub(crate) index_sats: bool,
+  pub(crate) index_spent_outputs: bool,
+  pub(crate) index_txids: bool,
+  pub(crate) inscriptions: u64,
   pub(crate) leaf_pages: u64,
   pub(crate) metadata_bytes: u64,
+  pub(crate) outputs_cached: u64,
   pub(crate) outputs_traversed: u64,
+  pub(crate) outputs_uncached: u64,
   pub(crate) page_size: usize,
   pub(crate) sat_ranges: u64,
+  pub(crate) sats_indexed: u64,
   pub(crate) stored_bytes: u64,
   pub(crate) transactions: Vec<TransactionInfo>,
   pub(crate) tree_height: u32,
@@ -148,23 +230,47 @@ impl<T> BitcoinCoreRpcResultExt<T> for Result<T, bitcoincore_rpc::Error> {
 }
 
 pub(crate) struct Index {
-  client: Client,
+  block_cache: Arc<BlockCache>,
+  block_client: ReloadableClient,
+  block_events: broadcast::Sender<BlockEvent>,
+  block_files: Option<Arc<LazyFileBlockSource>>,
+  client: ReloadableClient,
+  commit_interval: usize,
   database: Database,
   durability: redb::Durability,
   first_inscription_height: u64,
   genesis_block_coinbase_transaction: Transaction,
   genesis_block_coinbase_txid: Txid,
   height_limit: Option<u64>,
+  index_addresses: bool,
   index_runes: bool,
   index_sats: bool,
+  index_spent_outputs: bool,
+  index_txids: bool,
+  inscription_events: broadcast::Sender<InscriptionEvent>,
   options: Options,
   path: PathBuf,
+  range_cache_size: Option<usize>,
   unrecoverably_reorged: AtomicBool,
 }
 
 impl Index {
   pub(crate) fn open(options: &Options) -> Result<Self> {
-    let client = options.bitcoin_rpc_client()?;
+    let client = ReloadableClient::new(options.bitcoin_rpc_client()?);
+
+    // only block fetching retries, so only it gets a per-call timeout
+    let rpc_timeout = Duration::from_secs(options.rpc_timeout_seconds);
+
+    let block_client = ReloadableClient::new(Options::connect(
+      &options.rpc_url(),
+      options.auth()?,
+      Some(rpc_timeout),
+    )?);
+
+    if let Auth::CookieFile(cookie_file) = options.auth()? {
+      client.watch_cookie_file(options.rpc_url(), None, cookie_file.clone());
+      block_client.watch_cookie_file(options.rpc_url(), Some(rpc_timeout), cookie_file);
+    }
 
     let path = if let Some(path) = &options.index {
       path.clone()
@@ -210,8 +316,11 @@ impl Index {
       redb::Durability::Immediate
     };
 
+    let index_addresses;
     let index_runes;
     let index_sats;
+    let index_spent_outputs;
+    let index_txids;
 
     let database = match Database::builder()
       .set_cache_size(db_cache_size)
@@ -219,13 +328,19 @@ impl Index {
     {
       Ok(database) => {
         {
-          let tx = database.begin_read()?;
-          let schema_version = tx
+          let mut schema_version = database
+            .begin_read()?
             .open_table(STATISTIC_TO_COUNT)?
             .get(&Statistic::Schema.key())?
             .map(|x| x.value())
             .unwrap_or(0);
 
+          if schema_version < SCHEMA_VERSION {
+            schema_version = Self::migrate(&database, schema_version)?;
+          }
+
+          let tx = database.begin_read()?;
+
           match schema_version.cmp(&SCHEMA_VERSION) {
           cmp::Ordering::Less =>
             bail!(
@@ -243,6 +358,11 @@ impl Index {
 
           let statistics = tx.open_table(STATISTIC_TO_COUNT)?;
 
+          index_addresses = statistics
+            .get(&Statistic::IndexAddresses.key())?
+            .unwrap()
+            .value()
+            != 0;
           index_runes = statistics
             .get(&Statistic::IndexRunes.key())?
             .unwrap()
@@ -253,6 +373,16 @@ impl Index {
             .unwrap()
             .value()
             != 0;
+          index_spent_outputs = statistics
+            .get(&Statistic::IndexSpentOutputs.key())?
+            .unwrap()
+            .value()
+            != 0;
+          index_txids = statistics
+            .get(&Statistic::IndexTxids.key())?
+            .unwrap()
+            .value()
+            != 0;
         }
 
         database
@@ -266,20 +396,29 @@ impl Index {
 
         tx.set_durability(durability);
 
+        tx.open_multimap_table(CONTENT_HASH_TO_INSCRIPTION_IDS)?;
         tx.open_multimap_table(INSCRIPTION_ID_TO_CHILDREN)?;
         tx.open_multimap_table(SATPOINT_TO_INSCRIPTION_ID)?;
         tx.open_multimap_table(SAT_TO_INSCRIPTION_ID)?;
+        tx.open_multimap_table(SAT_TO_SATPOINT_HISTORY)?;
+        tx.open_multimap_table(SCRIPT_PUBKEY_TO_OUTPOINT)?;
         tx.open_table(HEIGHT_TO_BLOCK_HASH)?;
+        tx.open_table(HEIGHT_TO_BLOCK_HEADER)?;
+        tx.open_table(HEIGHT_TO_HASH_CHECKSUM)?;
         tx.open_table(HEIGHT_TO_LAST_SEQUENCE_NUMBER)?;
         tx.open_table(INSCRIPTION_ID_TO_INSCRIPTION_ENTRY)?;
         tx.open_table(INSCRIPTION_ID_TO_SATPOINT)?;
         tx.open_table(INSCRIPTION_NUMBER_TO_INSCRIPTION_ID)?;
         tx.open_table(OUTPOINT_TO_RUNE_BALANCES)?;
+        tx.open_table(OUTPOINT_TO_SCRIPT_PUBKEY)?;
         tx.open_table(OUTPOINT_TO_VALUE)?;
         tx.open_table(RUNE_ID_TO_RUNE_ENTRY)?;
         tx.open_table(RUNE_TO_RUNE_ID)?;
+        tx.open_table(SAT_TO_RARITY)?;
         tx.open_table(SAT_TO_SATPOINT)?;
         tx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ID)?;
+        tx.open_table(SPENT_OUTPOINT_TO_HEIGHT)?;
+        tx.open_table(TXID_TO_BLOCK_HEIGHT)?;
         tx.open_table(WRITE_TRANSACTION_STARTING_BLOCK_COUNT_TO_TIMESTAMP)?;
 
         {
@@ -290,14 +429,29 @@ impl Index {
             outpoint_to_sat_ranges.insert(&OutPoint::null().store(), [].as_slice())?;
           }
 
+          index_addresses = options.index_addresses;
           index_runes = options.index_runes();
           index_sats = options.index_sats;
+          index_spent_outputs = options.index_spent_outputs;
+          index_txids = options.index_txids;
 
+          statistics.insert(
+            &Statistic::IndexAddresses.key(),
+            &u64::from(options.index_addresses),
+          )?;
           statistics.insert(
             &Statistic::IndexRunes.key(),
             &u64::from(options.index_runes()),
           )?;
           statistics.insert(&Statistic::IndexSats.key(), &u64::from(options.index_sats))?;
+          statistics.insert(
+            &Statistic::IndexSpentOutputs.key(),
+            &u64::from(options.index_spent_outputs),
+          )?;
+          statistics.insert(
+            &Statistic::IndexTxids.key(),
+            &u64::from(options.index_txids),
+          )?;
           statistics.insert(&Statistic::Schema.key(), &SCHEMA_VERSION)?;
         }
 
@@ -312,7 +466,17 @@ impl Index {
 
     Ok(Self {
       genesis_block_coinbase_txid: genesis_block_coinbase_transaction.txid(),
+      block_cache: Arc::new(BlockCache::new(
+        options.block_cache_size.saturating_mul(1 << 20),
+      )),
+      block_client,
+      block_events: broadcast::channel(1024).0,
+      block_files: options
+        .blocks_dir
+        .clone()
+        .map(|dir| Arc::new(LazyFileBlockSource::new(dir, options.chain()))),
       client,
+      commit_interval: options.commit_interval,
       database,
       durability,
       first_inscription_height: options.first_inscription_height(),
@@ -321,21 +485,138 @@ impl Index {
       options: options.clone(),
       index_runes,
       index_sats,
+      index_spent_outputs,
+      index_addresses,
+      index_txids,
+      inscription_events: broadcast::channel(1024).0,
       path,
+      range_cache_size: options
+        .cache_size
+        .map(|cache_size| cache_size.saturating_mul(1 << 20)),
       unrecoverably_reorged: AtomicBool::new(false),
     })
   }
 
+  fn client(&self) -> Arc<Client> {
+    self.client.get()
+  }
+
   #[cfg(test)]
   fn set_durability(&mut self, durability: redb::Durability) {
     self.durability = durability;
   }
 
+  fn migrate(database: &Database, mut schema_version: u64) -> Result<u64> {
+    let migrations = MIGRATIONS
+      .iter()
+      .skip_while(|(version, _)| *version != schema_version)
+      .take_while(|(version, _)| *version < SCHEMA_VERSION)
+      .collect::<Vec<&(u64, Migration)>>();
+
+    if migrations.is_empty() {
+      return Ok(schema_version);
+    }
+
+    let wtx = database.begin_write()?;
+
+    for (version, migration) in migrations {
+      if *version != schema_version {
+        break;
+      }
+
+      log::info!("Migrating index from schema {version} to {}", version + 1);
+
+      migration(&wtx)?;
+
+      schema_version += 1;
+    }
+
+    wtx
+      .open_table(STATISTIC_TO_COUNT)?
+      .insert(&Statistic::Schema.key(), &schema_version)?;
+
+    wtx.commit()?;
+
+    Ok(schema_version)
+  }
+
+  fn migrate_index_spent_outputs(wtx: &WriteTransaction) -> Result {
+    // spent outputs used to be indexed along with sats
+    let mut statistics = wtx.open_table(STATISTIC_TO_COUNT)?;
+
+    let index_sats = statistics
+      .get(&Statistic::IndexSats.key())?
+      .map(|x| x.value())
+      .unwrap_or(0);
+
+    statistics.insert(&Statistic::IndexSpentOutputs.key(), &index_sats)?;
+
+    Ok(())
+  }
+
+  fn migrate_height_to_hash_checksum(wtx: &WriteTransaction) -> Result {
+    let mut height_to_hash_checksum = wtx.open_table(HEIGHT_TO_HASH_CHECKSUM)?;
+
+    for result in wtx.open_table(HEIGHT_TO_BLOCK_HASH)?.iter()? {
+      let (height, hash) = result?;
+      height_to_hash_checksum.insert(&height.value(), &Self::hash_checksum(hash.value()))?;
+    }
+
+    Ok(())
+  }
+
+  fn migrate_content_hash_to_inscription_ids(wtx: &WriteTransaction) -> Result {
+    // inscription content isn't stored, so existing inscriptions can't be hashed
+    if !wtx
+      .open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ID)?
+      .is_empty()?
+    {
+      bail!("index contains inscriptions without content hashes and must be rebuilt");
+    }
+
+    wtx.open_multimap_table(CONTENT_HASH_TO_INSCRIPTION_IDS)?;
+
+    Ok(())
+  }
+
+  fn migrate_sat_to_rarity(wtx: &WriteTransaction) -> Result {
+    let mut sat_to_rarity = wtx.open_table(SAT_TO_RARITY)?;
+
+    let index_sats = wtx
+      .open_table(STATISTIC_TO_COUNT)?
+      .get(&Statistic::IndexSats.key())?
+      .map(|x| x.value())
+      .unwrap_or(0)
+      != 0;
+
+    if !index_sats {
+      return Ok(());
+    }
+
+    let block_count = wtx
+      .open_table(HEIGHT_TO_BLOCK_HASH)?
+      .range(0..)?
+      .next_back()
+      .transpose()?
+      .map(|(height, _hash)| height.value() + 1)
+      .unwrap_or(0);
+
+    for height in 0..block_count {
+      let height = Height(height);
+      if height.subsidy() > 0 {
+        let sat = height.starting_sat();
+        sat_to_rarity.insert(&sat.n(), &u8::from(sat.rarity()))?;
+      }
+    }
+
+    Ok(())
+  }
+
   pub(crate) fn get_unspent_outputs(&self, _wallet: Wallet) -> Result<BTreeMap<OutPoint, Amount>> {
     let mut utxos = BTreeMap::new();
     utxos.extend(
       self
-        .client
+        .client()
         .list_unspent(None, None, None, None, None)?
         .into_iter()
         .map(|utxo| {
@@ -353,12 +634,14 @@ impl Index {
     }
 
     for JsonOutPoint { txid, vout } in self
-      .client
+      .client()
       .call::<Vec<JsonOutPoint>>("listlockunspent", &[])?
     {
       utxos.insert(
         OutPoint { txid, vout },
-        Amount::from_sat(self.client.get_raw_transaction(&txid, None)?.output[vout as usize].value),
+        Amount::from_sat(
+          self.client().get_raw_transaction(&txid, None)?.output[vout as usize].value,
+        ),
       );
     }
     let rtx = self.database.begin_read()?;
@@ -384,11 +667,20 @@ impl Index {
       .map(|outpoint| match self.list(outpoint)? {
         Some(List::Unspent(sat_ranges)) => Ok((outpoint, sat_ranges)),
         Some(List::Spent) => bail!("output {outpoint} in wallet but is spent according to index"),
+        Some(List::Unconfirmed) => bail!("output {outpoint} in wallet is not yet confirmed"),
         None => bail!("index has not seen {outpoint}"),
       })
       .collect()
   }
 
+  pub(crate) fn subscribe_block_events(&self) -> broadcast::Receiver<BlockEvent> {
+    self.block_events.subscribe()
+  }
+
+  pub(crate) fn subscribe_inscription_events(&self) -> broadcast::Receiver<InscriptionEvent> {
+    self.inscription_events.subscribe()
+  }
+
   pub(crate) fn has_sat_index(&self) -> bool {
     self.index_sats
   }
@@ -400,14 +692,14 @@ impl Index {
 
     let info = {
       let statistic_to_count = wtx.open_table(STATISTIC_TO_COUNT)?;
-      let sat_ranges = statistic_to_count
-        .get(&Statistic::SatRanges.key())?
-        .map(|x| x.value())
-        .unwrap_or(0);
-      let outputs_traversed = statistic_to_count
-        .get(&Statistic::OutputsTraversed.key())?
-        .map(|x| x.value())
-        .unwrap_or(0);
+      let statistic = |statistic: Statistic| -> Result<u64> {
+        Ok(
+          statistic_to_count
+            .get(&statistic.key())?
+            .map(|x| x.value())
+            .unwrap_or(0),
+        )
+      };
       Info {
         index_path: self.path.clone(),
         blocks_indexed: wtx
@@ -418,12 +710,23 @@ impl Index {
           .map(|(height, _hash)| height.value() + 1)
           .unwrap_or(0),
         branch_pages: stats.branch_pages(),
+        chain_blocks: self.chain_block_count()?,
+        commits: statistic(Statistic::Commits)?,
         fragmented_bytes: stats.fragmented_bytes(),
+        index_addresses: self.index_addresses,
         index_file_size: fs::metadata(&self.path)?.len(),
+        index_runes: self.index_runes,
+        index_sats: self.index_sats,
+        index_spent_outputs: self.index_spent_outputs,
+        index_txids: self.index_txids,
+        inscriptions: self.inscription_count()?,
         leaf_pages: stats.leaf_pages(),
         metadata_bytes: stats.metadata_bytes(),
-        sat_ranges,
-        outputs_traversed,
+        outputs_cached: statistic(Statistic::OutputsCached)?,
+        outputs_traversed: statistic(Statistic::OutputsTraversed)?,
+        outputs_uncached: statistic(Statistic::OutputsUncached)?,
+        sat_ranges: statistic(Statistic::SatRanges)?,
+        sats_indexed: self.total_indexed_sats()?,
         page_size: stats.page_size(),
         stored_bytes: stats.stored_bytes(),
         transactions: wtx
@@ -446,6 +749,86 @@ impl Index {
     Ok(info)
   }
 
+  pub(crate) fn gaps(&self, start: u64, end: u64) -> Result<Vec<(u64, u64)>> {
+    let rtx = self.begin_read()?;
+
+    let mut gaps = Vec::new();
+
+    let mut next = start;
+
+    for result in rtx.0.open_table(HEIGHT_TO_BLOCK_HASH)?.range(start..end)? {
+      let height = result?.0.value();
+
+      if height > next {
+        gaps.push((next, height));
+      }
+
+      next = height + 1;
+    }
+
+    Ok(gaps)
+  }
+
+  pub(crate) fn verify_chain_continuity(&self, start: u64, end: u64) -> Result {
+    let rtx = self.begin_read()?;
+
+    let mut prev: Option<(u64, BlockHash)> = None;
+
+    for result in rtx.0.open_table(HEIGHT_TO_BLOCK_HASH)?.range(start..end)? {
+      let (height, hash) = result?;
+      let height = height.value();
+      let hash = BlockHash::load(*hash.value());
+
+      if let Some((prev_height, prev_hash)) = prev {
+        if height != prev_height + 1 {
+          bail!("block {} missing from index", prev_height + 1);
+        }
+
+        let header = self
+          .block_header(hash)?
+          .ok_or_else(|| anyhow!("block {hash} at height {height} not found"))?;
+
+        if header.prev_blockhash != prev_hash {
+          bail!(
+            "chain broken at height {height}: block {hash} has previous block {}, but index has {prev_hash} at height {prev_height}",
+            header.prev_blockhash
+          );
+        }
+      }
+
+      prev = Some((height, hash));
+    }
+
+    Ok(())
+  }
+
+  pub(crate) fn hash_checksum(hash: &BlockHashValue) -> u32 {
+    crc32fast::hash(hash)
+  }
+
+  pub(crate) fn verify_checksums(&self, start: u64, end: u64) -> Result<Vec<u64>> {
+    let rtx = self.begin_read()?;
+
+    let height_to_hash_checksum = rtx.0.open_table(HEIGHT_TO_HASH_CHECKSUM)?;
+
+    let mut mismatches = Vec::new();
+
+    for result in rtx.0.open_table(HEIGHT_TO_BLOCK_HASH)?.range(start..end)? {
+      let (height, hash) = result?;
+      let height = height.value();
+
+      let checksum = height_to_hash_checksum
+        .get(&height)?
+        .map(|checksum| checksum.value());
+
+      if checksum != Some(Self::hash_checksum(hash.value())) {
+        mismatches.push(height);
+      }
+    }
+
+    Ok(mismatches)
+  }
+
   pub(crate) fn update(&self) -> Result {
     let mut updater = Updater::new(self)?;
 
@@ -474,6 +857,56 @@ impl Index {
     }
   }
 
+  pub(crate) fn compact(&mut self) -> Result<bool> {
+    let wtx = self.begin_write()?;
+
+    let savepoints = wtx.list_persistent_savepoints()?.collect::<Vec<u64>>();
+
+    if !savepoints.is_empty() {
+      log::warn!(
+        "Deleting {} reorg savepoints before compacting index",
+        savepoints.len()
+      );
+    }
+
+    let had_savepoints = !savepoints.is_empty();
+
+    for savepoint in savepoints {
+      wtx.delete_persistent_savepoint(savepoint)?;
+    }
+
+    wtx.commit()?;
+
+    let compacted = self.database.compact()?;
+
+    // savepoints keep pages alive, so they can't survive compaction, but
+    // recreate one at the current height so reorg recovery stays available
+    if had_savepoints {
+      let wtx = self.begin_write()?;
+      wtx.persistent_savepoint()?;
+      wtx.commit()?;
+    }
+
+    Ok(compacted)
+  }
+
+  pub(crate) fn for_each_output_sat_ranges(
+    &self,
+    mut f: impl FnMut(OutPoint, Vec<(u64, u64)>) -> Result,
+  ) -> Result {
+    let rtx = self.database.begin_read()?;
+
+    for result in rtx.open_table(OUTPOINT_TO_SAT_RANGES)?.iter()? {
+      let (outpoint, sat_ranges) = result?;
+      f(
+        Entry::load(*outpoint.value()),
+        decode_sat_ranges(sat_ranges.value())?,
+      )?;
+    }
+
+    Ok(())
+  }
+
   pub(crate) fn export(&self, filename: &String, include_addresses: bool) -> Result {
     let mut writer = BufWriter::new(File::create(filename)?);
     let rtx = self.database.begin_read()?;
@@ -570,24 +1003,103 @@ impl Index {
     Ok(())
   }
 
+  pub(crate) fn get_statistic(&self, statistic: Statistic) -> Result<u64> {
+    Ok(
+      self
+        .database
+        .begin_read()?
+        .open_table(STATISTIC_TO_COUNT)?
+        .get(&statistic.key())?
+        .map(|x| x.value())
+        .unwrap_or(0),
+    )
+  }
+
+  pub(crate) fn inscription_count(&self) -> Result<u64> {
+    Ok(
+      self.get_statistic(Statistic::BlessedInscriptions)?
+        + self.get_statistic(Statistic::CursedInscriptions)?,
+    )
+  }
+
   #[cfg(test)]
   pub(crate) fn statistic(&self, statistic: Statistic) -> u64 {
-    self
-      .database
-      .begin_read()
-      .unwrap()
-      .open_table(STATISTIC_TO_COUNT)
-      .unwrap()
-      .get(&statistic.key())
-      .unwrap()
-      .map(|x| x.value())
-      .unwrap_or(0)
+    self.get_statistic(statistic).unwrap()
   }
 
   pub(crate) fn block_count(&self) -> Result<u64> {
     self.begin_read()?.block_count()
   }
 
+  pub(crate) fn chain_block_count(&self) -> Result<u64> {
+    Ok(self.client().get_block_count()? + 1)
+  }
+
+  /// Number of blocks left to index before reaching the chain tip, or the
+  /// height limit if one is set.
+  pub(crate) fn blocks_behind(&self) -> Result<u64> {
+    let target = match self.height_limit {
+      Some(height_limit) => height_limit.min(self.chain_block_count()?),
+      None => self.chain_block_count()?,
+    };
+
+    Ok(target.saturating_sub(self.block_count()?))
+  }
+
+  /// Check that the hashes of the last `depth` indexed blocks still match the
+  /// hashes bitcoind reports at the same heights.
+  pub(crate) fn check_recent_block_hashes(&self, depth: u64) -> Result {
+    let Some(tip) = self.block_height()? else {
+      return Ok(());
+    };
+
+    for height in (0..depth).filter_map(|depth| tip.n().checked_sub(depth)) {
+      let index_block_hash = self.block_hash(Some(height))?;
+      let bitcoind_block_hash = self.client().get_block_hash(height).into_option()?;
+
+      if index_block_hash != bitcoind_block_hash {
+        bail!(
+          "indexed block {height} {} does not match bitcoind's {}, chain reorganized since index was last updated",
+          index_block_hash.map(|hash| hash.to_string()).unwrap_or_default(),
+          bitcoind_block_hash
+            .map(|hash| hash.to_string())
+            .unwrap_or_else(|| "missing block".into())
+        );
+      }
+    }
+
+    Ok(())
+  }
+
+  /// Blocks indexed per second between the starts of the last two write
+  /// transactions.
+  pub(crate) fn indexing_rate(&self) -> Result<Option<f64>> {
+    let rtx = self.begin_read()?;
+
+    let table = rtx
+      .0
+      .open_table(WRITE_TRANSACTION_STARTING_BLOCK_COUNT_TO_TIMESTAMP)?;
+
+    let mut last = table.iter()?.rev().take(2);
+
+    let (Some(newer), Some(older)) = (last.next().transpose()?, last.next().transpose()?) else {
+      return Ok(None);
+    };
+
+    let blocks = newer.0.value().saturating_sub(older.0.value());
+    let millis = newer.1.value().saturating_sub(older.1.value());
+
+    if blocks == 0 || millis == 0 {
+      return Ok(None);
+    }
+
+    Ok(Some(blocks as f64 / (millis as f64 / 1000.0)))
+  }
+
+  pub(crate) fn total_indexed_sats(&self) -> Result<u64> {
+    Ok(Height(self.block_count()?).starting_sat().n())
+  }
+
   pub(crate) fn block_height(&self) -> Result<Option<Height>> {
     self.begin_read()?.block_height()
   }
@@ -613,6 +1125,16 @@ impl Index {
     Ok(blocks)
   }
 
+  pub(crate) fn sat_range_for_height(&self, height: Height) -> Result<Option<(u64, u64)>> {
+    if height.subsidy() == 0 || self.block_hash(Some(height.n()))?.is_none() {
+      return Ok(None);
+    }
+
+    let start = height.starting_sat();
+
+    Ok(Some((start.n(), (start + height.subsidy()).n())))
+  }
+
   pub(crate) fn rare_sat_satpoints(&self) -> Result<Vec<(Sat, SatPoint)>> {
     let rtx = self.database.begin_read()?;
 
@@ -628,6 +1150,22 @@ impl Index {
     Ok(result)
   }
 
+  pub(crate) fn rare_sat_satpoints_in_block(&self, height: Height) -> Result<Vec<(Sat, SatPoint)>> {
+    let start = height.starting_sat().n();
+
+    self
+      .database
+      .begin_read()?
+      .open_table(SAT_TO_SATPOINT)?
+      .range(start..start + height.subsidy())?
+      .map(|result| {
+        result
+          .map(|(sat, satpoint)| (Sat(sat.value()), Entry::load(*satpoint.value())))
+          .map_err(|err| err.into())
+      })
+      .collect()
+  }
+
   pub(crate) fn rare_sat_satpoint(&self, sat: Sat) -> Result<Option<SatPoint>> {
     Ok(
       self
@@ -639,30 +1177,116 @@ impl Index {
     )
   }
 
-  pub(crate) fn get_rune_by_id(&self, id: RuneId) -> Result<Option<Rune>> {
+  /// Output values are only stored for outputs at or above the first
+  /// inscription height, so with a sat index the value is the size of the
+  /// output's sat ranges instead.
+  pub(crate) fn get_output_value(&self, outpoint: OutPoint) -> Result<Option<u64>> {
+    if outpoint == OutPoint::null() {
+      return Ok(None);
+    }
+
+    if self.index_sats {
+      return Ok(self.list_inner(outpoint.store())?.map(|sat_ranges| {
+        sat_ranges
+          .chunks_exact(11)
+          .map(|chunk| Lot::load(chunk.try_into().unwrap()).len())
+          .sum()
+      }));
+    }
+
     Ok(
       self
         .database
         .begin_read()?
-        .open_table(RUNE_ID_TO_RUNE_ENTRY)?
-        .get(&id.store())?
-        .map(|entry| RuneEntry::load(entry.value()).rune),
+        .open_table(OUTPOINT_TO_VALUE)?
+        .get(&outpoint.store())?
+        .map(|value| value.value()),
     )
   }
 
-  pub(crate) fn rune(&self, rune: Rune) -> Result<Option<(RuneId, RuneEntry)>> {
-    let rtx = self.database.begin_read()?;
-
-    let entry = match rtx.open_table(RUNE_TO_RUNE_ID)?.get(rune.0)? {
-      Some(id) => rtx
-        .open_table(RUNE_ID_TO_RUNE_ENTRY)?
-        .get(id.value())?
-        .map(|entry| (RuneId::load(id.value()), RuneEntry::load(entry.value()))),
-      None => None,
-    };
+  /// Satpoint history is only tracked for non-common sats. Returns `None` for
+  /// common sats, whose movements are not recorded.
+  pub(crate) fn rare_sat_history(&self, sat: Sat) -> Result<Option<Vec<SatPoint>>> {
+    if sat.rarity() == Rarity::Common {
+      return Ok(None);
+    }
 
-    Ok(entry)
-  }
+    self
+      .database
+      .begin_read()?
+      .open_multimap_table(SAT_TO_SATPOINT_HISTORY)?
+      .get(&sat.n())?
+      .map(|result| {
+        result
+          .map(|entry| <(u64, SatPoint)>::load(*entry.value()).1)
+          .map_err(|err| err.into())
+      })
+      .collect::<Result<Vec<SatPoint>>>()
+      .map(Some)
+  }
+
+  pub(crate) fn inscriptions_in_transaction(&self, txid: Txid) -> Result<Vec<InscriptionId>> {
+    let start = InscriptionId { txid, index: 0 }.store();
+    let end = InscriptionId {
+      txid,
+      index: u32::MAX,
+    }
+    .store();
+
+    self
+      .database
+      .begin_read()?
+      .open_table(INSCRIPTION_ID_TO_INSCRIPTION_ENTRY)?
+      .range::<&InscriptionIdValue>(&start..=&end)?
+      .map(|result| {
+        result
+          .map(|(id, _entry)| InscriptionId::load(*id.value()))
+          .map_err(|err| err.into())
+      })
+      .collect()
+  }
+
+  pub(crate) fn inscriptions_with_content_hash(
+    &self,
+    hash: [u8; 32],
+  ) -> Result<Vec<InscriptionId>> {
+    self
+      .database
+      .begin_read()?
+      .open_multimap_table(CONTENT_HASH_TO_INSCRIPTION_IDS)?
+      .get(&hash)?
+      .map(|result| {
+        result
+          .map(|id| InscriptionId::load(*id.value()))
+          .map_err(|err| err.into())
+      })
+      .collect()
+  }
+
+  pub(crate) fn get_rune_by_id(&self, id: RuneId) -> Result<Option<Rune>> {
+    Ok(
+      self
+        .database
+        .begin_read()?
+        .open_table(RUNE_ID_TO_RUNE_ENTRY)?
+        .get(&id.store())?
+        .map(|entry| RuneEntry::load(entry.value()).rune),
+    )
+  }
+
+  pub(crate) fn rune(&self, rune: Rune) -> Result<Option<(RuneId, RuneEntry)>> {
+    let rtx = self.database.begin_read()?;
+
+    let entry = match rtx.open_table(RUNE_TO_RUNE_ID)?.get(rune.0)? {
+      Some(id) => rtx
+        .open_table(RUNE_ID_TO_RUNE_ENTRY)?
+        .get(id.value())?
+        .map(|entry| (RuneId::load(id.value()), RuneEntry::load(entry.value()))),
+      None => None,
+    };
+
+    Ok(entry)
+  }
 
   pub(crate) fn runes(&self) -> Result<Vec<(RuneId, RuneEntry)>> {
     let mut entries = Vec::new();
@@ -755,26 +1379,26 @@ impl Index {
   }
 
   pub(crate) fn block_header(&self, hash: BlockHash) -> Result<Option<Header>> {
-    self.client.get_block_header(&hash).into_option()
+    self.client().get_block_header(&hash).into_option()
   }
 
   pub(crate) fn block_header_info(&self, hash: BlockHash) -> Result<Option<GetBlockHeaderResult>> {
-    self.client.get_block_header_info(&hash).into_option()
+    self.client().get_block_header_info(&hash).into_option()
   }
 
   pub(crate) fn get_block_by_height(&self, height: u64) -> Result<Option<Block>> {
     Ok(
       self
-        .client
+        .client()
         .get_block_hash(height)
         .into_option()?
-        .map(|hash| self.client.get_block(&hash))
+        .map(|hash| self.client().get_block(&hash))
         .transpose()?,
     )
   }
 
   pub(crate) fn get_block_by_hash(&self, hash: BlockHash) -> Result<Option<Block>> {
-    self.client.get_block(&hash).into_option()
+    self.client().get_block(&hash).into_option()
   }
 
   pub(crate) fn get_children_by_inscription_id(
@@ -934,14 +1558,68 @@ impl Index {
     if txid == self.genesis_block_coinbase_txid {
       Ok(Some(self.genesis_block_coinbase_transaction.clone()))
     } else {
-      self.client.get_raw_transaction(&txid, None).into_option()
+      self.client().get_raw_transaction(&txid, None).into_option()
     }
   }
 
+  pub(crate) fn block_height_for_txid(&self, txid: Txid) -> Result<Option<u64>> {
+    if !self.index_txids {
+      bail!("transaction index not found, rebuild the index with `--index-txids`");
+    }
+
+    Ok(
+      self
+        .database
+        .begin_read()?
+        .open_table(TXID_TO_BLOCK_HEIGHT)?
+        .get(&txid.store())?
+        .map(|height| height.value()),
+    )
+  }
+
+  pub(crate) fn has_address_index(&self) -> bool {
+    self.index_addresses
+  }
+
+  pub(crate) fn outpoints_for_address(&self, address: &Address) -> Result<Vec<OutPoint>> {
+    if !self.index_addresses {
+      bail!("address index not found, rebuild the index with `--index-addresses`");
+    }
+
+    self
+      .database
+      .begin_read()?
+      .open_multimap_table(SCRIPT_PUBKEY_TO_OUTPOINT)?
+      .get(address.script_pubkey().as_bytes())?
+      .map(|outpoint| Ok(Entry::load(*outpoint?.value())))
+      .collect()
+  }
+
+  /// Inscriptions on every unspent output paying to `address`.
+  pub(crate) fn all_inscriptions_for_address(
+    &self,
+    address: &Address,
+  ) -> Result<Vec<InscriptionId>> {
+    let mut inscriptions = Vec::new();
+
+    for outpoint in self.outpoints_for_address(address)? {
+      inscriptions.extend(self.get_inscriptions_on_output(outpoint)?);
+    }
+
+    Ok(inscriptions)
+  }
+
   pub(crate) fn get_transaction_blockhash(&self, txid: Txid) -> Result<Option<BlockHash>> {
+    if self.index_txids {
+      return match self.block_height_for_txid(txid)? {
+        Some(height) => self.block_hash(Some(height)),
+        None => Ok(None),
+      };
+    }
+
     Ok(
       self
-        .client
+        .client()
         .get_raw_transaction_info(&txid, None)
         .into_option()?
         .and_then(|info| {
@@ -957,7 +1635,7 @@ impl Index {
   pub(crate) fn is_transaction_in_active_chain(&self, txid: Txid) -> Result<bool> {
     Ok(
       self
-        .client
+        .client()
         .get_raw_transaction_info(&txid, None)
         .into_option()?
         .and_then(|info| info.in_active_chain)
@@ -965,6 +1643,16 @@ impl Index {
     )
   }
 
+  pub(crate) fn is_transaction_in_mempool(&self, txid: Txid) -> Result<bool> {
+    match self.client().get_mempool_entry(&txid) {
+      Ok(_) => Ok(true),
+      Err(bitcoincore_rpc::Error::JsonRpc(bitcoincore_rpc::jsonrpc::error::Error::Rpc(
+        bitcoincore_rpc::jsonrpc::error::RpcError { code: -5, .. },
+      ))) => Ok(false),
+      Err(err) => Err(err.into()),
+    }
+  }
+
   pub(crate) fn find(&self, sat: u64) -> Result<Option<SatPoint>> {
     let rtx = self.begin_read()?;
 
@@ -978,14 +1666,14 @@ impl Index {
       let (key, value) = range?;
       let mut offset = 0;
       for chunk in value.value().chunks_exact(11) {
-        let (start, end) = SatRange::load(chunk.try_into().unwrap());
-        if start <= sat && sat < end {
+        let lot = Lot::load(chunk.try_into().unwrap());
+        if lot.contains(sat) {
           return Ok(Some(SatPoint {
             outpoint: Entry::load(*key.value()),
-            offset: offset + sat - start,
+            offset: offset + sat - lot.start,
           }));
         }
-        offset += end - start;
+        offset += lot.len();
       }
     }
 
@@ -1015,7 +1703,7 @@ impl Index {
 
       let mut offset = 0;
       for sat_range in sat_ranges_entry.value().chunks_exact(11) {
-        let (start, end) = SatRange::load(sat_range.try_into().unwrap());
+        let Lot { start, end } = Lot::load(sat_range.try_into().unwrap());
 
         if end > range_start && start < range_end {
           let overlap_start = start.max(range_start);
@@ -1064,15 +1752,18 @@ impl Index {
     let sat_ranges = self.list_inner(array)?;
 
     match sat_ranges {
-      Some(sat_ranges) => Ok(Some(List::Unspent(
-        sat_ranges
-          .chunks_exact(11)
-          .map(|chunk| SatRange::load(chunk.try_into().unwrap()))
-          .collect(),
-      ))),
+      Some(sat_ranges) => Ok(Some(List::Unspent(decode_sat_ranges(&sat_ranges)?))),
       None => {
-        if self.is_transaction_in_active_chain(outpoint.txid)? {
+        let confirmed = if self.index_txids {
+          self.block_height_for_txid(outpoint.txid)?.is_some()
+        } else {
+          self.is_transaction_in_active_chain(outpoint.txid)?
+        };
+
+        if confirmed {
           Ok(Some(List::Spent))
+        } else if self.is_transaction_in_mempool(outpoint.txid)? {
+          Ok(Some(List::Unconfirmed))
         } else {
           Ok(None)
         }
@@ -1080,9 +1771,70 @@ impl Index {
     }
   }
 
+  pub(crate) fn has_spent_output_index(&self) -> bool {
+    self.index_spent_outputs
+  }
+
+  pub(crate) fn spent_height(&self, outpoint: OutPoint) -> Result<Option<u64>> {
+    if !self.index_spent_outputs {
+      bail!("spent output index not found, rebuild the index with `--index-spent-outputs`");
+    }
+
+    Ok(
+      self
+        .database
+        .begin_read()?
+        .open_table(SPENT_OUTPOINT_TO_HEIGHT)?
+        .get(&outpoint.store())?
+        .map(|height| height.value()),
+    )
+  }
+
+  pub(crate) fn rarity(&self, sat: Sat) -> Result<Rarity> {
+    if !self.index_sats || sat.n() >= self.total_indexed_sats()? {
+      return Ok(sat.rarity());
+    }
+
+    Ok(
+      self
+        .database
+        .begin_read()?
+        .open_table(SAT_TO_RARITY)?
+        .get(&sat.n())?
+        .map(|rarity| Rarity::try_from(rarity.value()).map_err(|n| anyhow!("invalid rarity: {n}")))
+        .transpose()?
+        .unwrap_or(Rarity::Common),
+    )
+  }
+
+  pub(crate) fn sat_at_satpoint(&self, satpoint: SatPoint) -> Result<Option<Sat>> {
+    if !self.index_sats {
+      return Ok(None);
+    }
+
+    let Some(sat_ranges) = self.list_inner(satpoint.outpoint.store())? else {
+      return Ok(None);
+    };
+
+    let mut offset = satpoint.offset;
+    for (start, end) in decode_sat_ranges(&sat_ranges)? {
+      let size = end - start;
+      if offset < size {
+        return Ok(Some(Sat(start + offset)));
+      }
+      offset -= size;
+    }
+
+    Ok(None)
+  }
+
   pub(crate) fn block_time(&self, height: Height) -> Result<Blocktime> {
     let height = height.n();
 
+    if let Some(header) = self.begin_read()?.block_header(height)? {
+      return Ok(Blocktime::confirmed(header.time));
+    }
+
     match self.get_block_by_height(height)? {
       Some(block) => Ok(Blocktime::confirmed(block.header.time)),
       None => {
@@ -1177,6 +1929,69 @@ impl Index {
     Ok((inscriptions, prev, next, lowest, highest))
   }
 
+  /// Up to `n` inscriptions in sequence number order starting at sequence
+  /// number `start`, along with the total number of inscriptions and the
+  /// sequence number that the next page starts at, if there is one.
+  pub(crate) fn get_inscriptions_paginated(
+    &self,
+    start: u64,
+    n: usize,
+  ) -> Result<(Vec<InscriptionId>, u64, Option<u64>)> {
+    let rtx = self.database.begin_read()?;
+
+    let sequence_number_to_inscription_id = rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ID)?;
+
+    let total = sequence_number_to_inscription_id.len()?;
+
+    let mut inscriptions = Vec::new();
+    let mut next = None;
+
+    for result in sequence_number_to_inscription_id.range(start..)? {
+      let (number, id) = result?;
+
+      if inscriptions.len() == n {
+        next = Some(number.value());
+        break;
+      }
+
+      inscriptions.push(InscriptionId::load(*id.value()));
+    }
+
+    Ok((inscriptions, total, next))
+  }
+
+  /// Number of blocks indexed after the most recent block containing an
+  /// inscription, or `None` if nothing has been inscribed.
+  pub(crate) fn blocks_since_last_inscription(&self) -> Result<Option<u64>> {
+    let rtx = self.database.begin_read()?;
+
+    let height_to_last_sequence_number = rtx.open_table(HEIGHT_TO_LAST_SEQUENCE_NUMBER)?;
+
+    let mut entries = height_to_last_sequence_number.iter()?.rev();
+
+    let Some((top, last)) = entries
+      .next()
+      .transpose()?
+      .map(|(height, sequence_number)| (height.value(), sequence_number.value()))
+    else {
+      return Ok(None);
+    };
+
+    let mut inscribed = top;
+
+    for entry in entries {
+      let (height, sequence_number) = entry?;
+
+      if sequence_number.value() < last {
+        return Ok(Some(top - inscribed));
+      }
+
+      inscribed = height.value();
+    }
+
+    Ok((last > 0).then_some(top - inscribed))
+  }
+
   pub(crate) fn get_inscriptions_in_block(&self, block_height: u64) -> Result<Vec<InscriptionId>> {
     let rtx = self.database.begin_read()?;
 
@@ -1206,6 +2021,46 @@ impl Index {
       .collect::<Result<Vec<InscriptionId>>>()
   }
 
+  pub(crate) fn get_inscription_entries_in_block(
+    &self,
+    block_height: u64,
+  ) -> Result<Vec<(InscriptionId, InscriptionEntry)>> {
+    let rtx = self.database.begin_read()?;
+
+    let height_to_last_sequence_number = rtx.open_table(HEIGHT_TO_LAST_SEQUENCE_NUMBER)?;
+    let sequence_number_to_inscription_id = rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ID)?;
+    let inscription_id_to_inscription_entry =
+      rtx.open_table(INSCRIPTION_ID_TO_INSCRIPTION_ENTRY)?;
+
+    let Some(newest_sequence_number) = height_to_last_sequence_number
+      .get(&block_height)?
+      .map(|ag| ag.value())
+    else {
+      return Ok(Vec::new());
+    };
+
+    let oldest_sequence_number = height_to_last_sequence_number
+      .get(block_height.saturating_sub(1))?
+      .map(|ag| ag.value())
+      .unwrap_or(0);
+
+    (oldest_sequence_number..newest_sequence_number)
+      .map(|num| {
+        let inscription_id = sequence_number_to_inscription_id
+          .get(&num)?
+          .map(|inscription_id| InscriptionId::load(*inscription_id.value()))
+          .ok_or_else(|| anyhow!("could not find inscription for sequence number {num}"))?;
+
+        let entry = inscription_id_to_inscription_entry
+          .get(&inscription_id.store())?
+          .map(|entry| InscriptionEntry::load(entry.value()))
+          .ok_or_else(|| anyhow!("could not find entry for inscription {inscription_id}"))?;
+
+        Ok((inscription_id, entry))
+      })
+      .collect()
+  }
+
   pub(crate) fn get_highest_paying_inscriptions_in_block(
     &self,
     block_height: u64,
@@ -1378,107 +2233,530 @@ impl Index {
       }
     }
 
-    for range in rtx
-      .open_multimap_table(SAT_TO_INSCRIPTION_ID)
-      .unwrap()
-      .iter()
-      .into_iter()
-    {
-      for entry in range.into_iter() {
-        let (_sat, ids) = entry.unwrap();
-        assert!(!ids
-          .into_iter()
-          .any(|id| InscriptionId::load(*id.unwrap().value()) == inscription_id))
-      }
-    }
+    for range in rtx
+      .open_multimap_table(SAT_TO_INSCRIPTION_ID)
+      .unwrap()
+      .iter()
+      .into_iter()
+    {
+      for entry in range.into_iter() {
+        let (_sat, ids) = entry.unwrap();
+        assert!(!ids
+          .into_iter()
+          .any(|id| InscriptionId::load(*id.unwrap().value()) == inscription_id))
+      }
+    }
+  }
+
+  fn inscriptions_on_output_unordered<'a: 'tx, 'tx>(
+    satpoint_to_id: &'a impl ReadableMultimapTable<&'static SatPointValue, &'static InscriptionIdValue>,
+    outpoint: OutPoint,
+  ) -> Result<impl Iterator<Item = (SatPoint, InscriptionId)> + 'tx> {
+    let start = SatPoint {
+      outpoint,
+      offset: 0,
+    }
+    .store();
+
+    let end = SatPoint {
+      outpoint,
+      offset: u64::MAX,
+    }
+    .store();
+
+    let mut inscriptions = Vec::new();
+
+    for range in satpoint_to_id.range::<&[u8; 44]>(&start..=&end)? {
+      let (satpoint, ids) = range?;
+      for id_result in ids {
+        let id = id_result?;
+        inscriptions.push((Entry::load(*satpoint.value()), Entry::load(*id.value())));
+      }
+    }
+
+    Ok(inscriptions.into_iter())
+  }
+
+  fn inscriptions_on_output_ordered<'a: 'tx, 'tx>(
+    inscription_id_to_entry: &'a impl ReadableTable<&'static InscriptionIdValue, InscriptionEntryValue>,
+    satpoint_to_id: &'a impl ReadableMultimapTable<&'static SatPointValue, &'static InscriptionIdValue>,
+    outpoint: OutPoint,
+  ) -> Result<Vec<(SatPoint, InscriptionId)>> {
+    let mut result = Self::inscriptions_on_output_unordered(satpoint_to_id, outpoint)?
+      .collect::<Vec<(SatPoint, InscriptionId)>>();
+
+    if result.len() <= 1 {
+      return Ok(result);
+    }
+
+    result.sort_by_key(|(_satpoint, inscription_id)| {
+      match inscription_id_to_entry
+        .get(&inscription_id.store())
+        .unwrap()
+        .map(|entry| InscriptionEntry::load(entry.value()))
+      {
+        Some(entry) => entry.sequence_number + 1, // remove at next index refactor
+        None => 0,
+      }
+    });
+
+    Ok(result)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {
+    super::*,
+    crate::index::testing::Context,
+    bitcoin::secp256k1::rand::{self, RngCore},
+  };
+
+  #[test]
+  fn height_limit() {
+    {
+      let context = Context::builder().args(["--height-limit", "0"]).build();
+      context.mine_blocks(1);
+      assert_eq!(context.index.block_height().unwrap(), None);
+      assert_eq!(context.index.block_count().unwrap(), 0);
+    }
+
+    {
+      let context = Context::builder().args(["--height-limit", "1"]).build();
+      context.mine_blocks(1);
+      assert_eq!(context.index.block_height().unwrap(), Some(Height(0)));
+      assert_eq!(context.index.block_count().unwrap(), 1);
+    }
+
+    {
+      let context = Context::builder().args(["--height-limit", "2"]).build();
+      context.mine_blocks(2);
+      assert_eq!(context.index.block_height().unwrap(), Some(Height(1)));
+      assert_eq!(context.index.block_count().unwrap(), 2);
+    }
+  }
+
+  #[test]
+  fn commit_interval() {
+    {
+      let context = Context::builder().build();
+      assert_eq!(context.index.statistic(Statistic::Commits), 1);
+      context.mine_blocks(4);
+      assert_eq!(context.index.statistic(Statistic::Commits), 2);
+    }
+
+    {
+      let context = Context::builder().args(["--commit-interval", "2"]).build();
+      assert_eq!(context.index.statistic(Statistic::Commits), 1);
+      context.mine_blocks(4);
+      assert_eq!(context.index.statistic(Statistic::Commits), 3);
+      context.mine_blocks(1);
+      assert_eq!(context.index.statistic(Statistic::Commits), 4);
+    }
+  }
+
+  #[test]
+  fn full_range_cache_is_flushed() {
+    {
+      let context = Context::builder().args(["--cache-size", "0"]).build();
+      assert_eq!(context.index.statistic(Statistic::Commits), 1);
+      context.mine_blocks(4);
+      assert_eq!(context.index.statistic(Statistic::Commits), 2);
+    }
+
+    {
+      let context = Context::builder()
+        .args(["--index-sats", "--cache-size", "0"])
+        .build();
+      assert_eq!(context.index.statistic(Statistic::Commits), 1);
+      context.mine_blocks(4);
+      assert_eq!(context.index.statistic(Statistic::Commits), 5);
+    }
+
+    {
+      let context = Context::builder()
+        .args(["--index-sats", "--cache-size", "1"])
+        .build();
+      context.mine_blocks(4);
+      assert_eq!(context.index.statistic(Statistic::Commits), 2);
+    }
+  }
+
+  #[test]
+  fn block_headers_are_stored() {
+    let context = Context::builder().build();
+
+    let blocks = context.mine_blocks(2);
+
+    let rtx = context.index.begin_read().unwrap();
+
+    assert_eq!(rtx.block_header(1).unwrap(), Some(blocks[0].header));
+    assert_eq!(rtx.block_header(2).unwrap(), Some(blocks[1].header));
+    assert_eq!(rtx.block_header(3).unwrap(), None);
+  }
+
+  #[test]
+  fn sat_range_for_height() {
+    let context = Context::builder().build();
+
+    context.mine_blocks(1);
+
+    assert_eq!(
+      context.index.sat_range_for_height(Height(0)).unwrap(),
+      Some((0, 50 * COIN_VALUE))
+    );
+    assert_eq!(
+      context.index.sat_range_for_height(Height(1)).unwrap(),
+      Some((50 * COIN_VALUE, 100 * COIN_VALUE))
+    );
+    assert_eq!(context.index.sat_range_for_height(Height(2)).unwrap(), None);
+    assert_eq!(
+      context
+        .index
+        .sat_range_for_height(Height(SUBSIDY_HALVING_INTERVAL * 33))
+        .unwrap(),
+      None
+    );
+  }
+
+  #[test]
+  fn total_indexed_sats() {
+    let context = Context::builder().build();
+
+    assert_eq!(context.index.total_indexed_sats().unwrap(), 50 * COIN_VALUE);
+
+    context.mine_blocks(2);
+
+    assert_eq!(
+      context.index.total_indexed_sats().unwrap(),
+      150 * COIN_VALUE
+    );
+  }
+
+  #[test]
+  fn spent_height() {
+    let context = Context::builder().arg("--index-spent-outputs").build();
+
+    let outpoint = OutPoint {
+      txid: context.mine_blocks(1)[0].txdata[0].txid(),
+      vout: 0,
+    };
+
+    assert_eq!(context.index.spent_height(outpoint).unwrap(), None);
+
+    context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, Default::default())],
+      ..Default::default()
+    });
+
+    context.mine_blocks(1);
+
+    assert_eq!(context.index.spent_height(outpoint).unwrap(), Some(2));
+  }
+
+  #[test]
+  fn spent_height_requires_spent_output_index() {
+    let context = Context::builder().arg("--index-sats").build();
+
+    let outpoint = OutPoint {
+      txid: context.mine_blocks(1)[0].txdata[0].txid(),
+      vout: 0,
+    };
+
+    assert_eq!(
+      context
+        .index
+        .spent_height(outpoint)
+        .unwrap_err()
+        .to_string(),
+      "spent output index not found, rebuild the index with `--index-spent-outputs`"
+    );
+  }
+
+  #[test]
+  fn sat_at_satpoint() {
+    let context = Context::builder().arg("--index-sats").build();
+
+    let txid = context.mine_blocks(1)[0].txdata[0].txid();
+
+    let satpoint = |offset| SatPoint {
+      outpoint: OutPoint { txid, vout: 0 },
+      offset,
+    };
+
+    assert_eq!(
+      context.index.sat_at_satpoint(satpoint(0)).unwrap(),
+      Some(Sat(50 * COIN_VALUE))
+    );
+    assert_eq!(
+      context.index.sat_at_satpoint(satpoint(1)).unwrap(),
+      Some(Sat(50 * COIN_VALUE + 1))
+    );
+    assert_eq!(
+      context
+        .index
+        .sat_at_satpoint(satpoint(50 * COIN_VALUE))
+        .unwrap(),
+      None
+    );
+
+    context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, Default::default())],
+      ..Default::default()
+    });
+
+    context.mine_blocks(1);
+
+    assert_eq!(context.index.sat_at_satpoint(satpoint(0)).unwrap(), None);
+  }
+
+  #[test]
+  fn rarity_is_read_from_table() {
+    let context = Context::builder().arg("--index-sats").build();
+
+    context.mine_blocks(1);
+
+    assert_eq!(
+      context
+        .index
+        .database
+        .begin_read()
+        .unwrap()
+        .open_table(SAT_TO_RARITY)
+        .unwrap()
+        .len()
+        .unwrap(),
+      2
+    );
+
+    assert_eq!(context.index.rarity(Sat(0)).unwrap(), Rarity::Mythic);
+    assert_eq!(context.index.rarity(Sat(1)).unwrap(), Rarity::Common);
+    assert_eq!(
+      context.index.rarity(Sat(50 * COIN_VALUE)).unwrap(),
+      Rarity::Uncommon
+    );
+    assert_eq!(
+      context.index.rarity(Sat(100 * COIN_VALUE)).unwrap(),
+      Rarity::Uncommon
+    );
+  }
+
+  #[test]
+  fn rare_sat_satpoints_in_block() {
+    let context = Context::builder().arg("--index-sats").build();
+
+    let txid = context.mine_blocks(1)[0].txdata[0].txid();
+
+    context.mine_blocks(1);
+
+    assert_eq!(
+      context
+        .index
+        .rare_sat_satpoints_in_block(Height(1))
+        .unwrap(),
+      [(
+        Sat(50 * COIN_VALUE),
+        SatPoint {
+          outpoint: OutPoint { txid, vout: 0 },
+          offset: 0,
+        }
+      )]
+    );
+
+    assert_eq!(
+      context
+        .index
+        .rare_sat_satpoints_in_block(Height(3))
+        .unwrap(),
+      []
+    );
+  }
+
+  #[test]
+  fn outpoints_for_address_requires_address_index() {
+    let context = Context::builder().build();
+
+    assert_eq!(
+      context
+        .index
+        .outpoints_for_address(
+          &"bcrt1qw508d6qejxtdg4y5r3zarvary0c5xw7kygt080"
+            .parse::<Address<NetworkUnchecked>>()
+            .unwrap()
+            .assume_checked()
+        )
+        .unwrap_err()
+        .to_string(),
+      "address index not found, rebuild the index with `--index-addresses`"
+    );
+  }
+
+  #[test]
+  fn outpoints_for_address() {
+    let context = Context::builder().arg("--index-addresses").build();
+
+    let address = "bcrt1qw508d6qejxtdg4y5r3zarvary0c5xw7kygt080"
+      .parse::<Address<NetworkUnchecked>>()
+      .unwrap()
+      .assume_checked();
+
+    context.mine_blocks(1);
+
+    assert_eq!(context.index.outpoints_for_address(&address).unwrap(), []);
+
+    context
+      .index
+      .client()
+      .send_to_address(
+        &address,
+        Amount::from_sat(COIN_VALUE),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+      )
+      .unwrap();
+
+    let txid = context.rpc_server.mempool()[0].txid();
+
+    context.mine_blocks(1);
+
+    assert_eq!(
+      context.index.outpoints_for_address(&address).unwrap(),
+      [OutPoint { txid, vout: 0 }, OutPoint { txid, vout: 1 }]
+    );
+
+    context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 1, 0, Default::default())],
+      ..Default::default()
+    });
+
+    context.mine_blocks(1);
+
+    assert_eq!(
+      context.index.outpoints_for_address(&address).unwrap(),
+      [OutPoint { txid, vout: 1 }]
+    );
+  }
+
+  #[test]
+  fn block_height_for_txid_requires_txid_index() {
+    let context = Context::builder().build();
+
+    let txid = context.mine_blocks(1)[0].txdata[0].txid();
+
+    assert_eq!(
+      context
+        .index
+        .block_height_for_txid(txid)
+        .unwrap_err()
+        .to_string(),
+      "transaction index not found, rebuild the index with `--index-txids`"
+    );
   }
 
-  fn inscriptions_on_output_unordered<'a: 'tx, 'tx>(
-    satpoint_to_id: &'a impl ReadableMultimapTable<&'static SatPointValue, &'static InscriptionIdValue>,
-    outpoint: OutPoint,
-  ) -> Result<impl Iterator<Item = (SatPoint, InscriptionId)> + 'tx> {
-    let start = SatPoint {
-      outpoint,
-      offset: 0,
-    }
-    .store();
+  #[test]
+  fn block_height_for_txid() {
+    let context = Context::builder().arg("--index-txids").build();
 
-    let end = SatPoint {
-      outpoint,
-      offset: u64::MAX,
-    }
-    .store();
+    context.mine_blocks(1);
 
-    let mut inscriptions = Vec::new();
+    let txid = context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, Default::default())],
+      ..Default::default()
+    });
 
-    for range in satpoint_to_id.range::<&[u8; 44]>(&start..=&end)? {
-      let (satpoint, ids) = range?;
-      for id_result in ids {
-        let id = id_result?;
-        inscriptions.push((Entry::load(*satpoint.value()), Entry::load(*id.value())));
-      }
-    }
+    assert_eq!(context.index.block_height_for_txid(txid).unwrap(), None);
 
-    Ok(inscriptions.into_iter())
+    let blocks = context.mine_blocks(1);
+
+    assert_eq!(context.index.block_height_for_txid(txid).unwrap(), Some(2));
+    assert_eq!(
+      context
+        .index
+        .block_height_for_txid(blocks[0].txdata[0].txid())
+        .unwrap(),
+      Some(2)
+    );
+    assert_eq!(
+      context.index.get_transaction_blockhash(txid).unwrap(),
+      Some(blocks[0].block_hash())
+    );
   }
 
-  fn inscriptions_on_output_ordered<'a: 'tx, 'tx>(
-    inscription_id_to_entry: &'a impl ReadableTable<&'static InscriptionIdValue, InscriptionEntryValue>,
-    satpoint_to_id: &'a impl ReadableMultimapTable<&'static SatPointValue, &'static InscriptionIdValue>,
-    outpoint: OutPoint,
-  ) -> Result<Vec<(SatPoint, InscriptionId)>> {
-    let mut result = Self::inscriptions_on_output_unordered(satpoint_to_id, outpoint)?
-      .collect::<Vec<(SatPoint, InscriptionId)>>();
+  #[test]
+  fn range_cache_hits_and_misses_are_tracked() {
+    let context = Context::builder().arg("--index-sats").build();
 
-    if result.len() <= 1 {
-      return Ok(result);
-    }
+    context.mine_blocks(1);
 
-    result.sort_by_key(|(_satpoint, inscription_id)| {
-      match inscription_id_to_entry
-        .get(&inscription_id.store())
-        .unwrap()
-        .map(|entry| InscriptionEntry::load(entry.value()))
-      {
-        Some(entry) => entry.sequence_number + 1, // remove at next index refactor
-        None => 0,
-      }
+    context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, Default::default())],
+      ..Default::default()
     });
 
-    Ok(result)
+    context.mine_blocks(1);
+
+    assert_eq!(context.index.statistic(Statistic::OutputsCached), 0);
+    assert_eq!(context.index.statistic(Statistic::OutputsUncached), 1);
   }
-}
 
-#[cfg(test)]
-mod tests {
-  use {
-    super::*,
-    crate::index::testing::Context,
-    bitcoin::secp256k1::rand::{self, RngCore},
-  };
+  #[test]
+  fn gaps() {
+    let context = Context::builder().build();
+
+    context.mine_blocks(5);
+
+    assert_eq!(context.index.gaps(0, 6).unwrap(), Vec::new());
+
+    let wtx = context.index.begin_write().unwrap();
+    let mut height_to_block_hash = wtx.open_table(HEIGHT_TO_BLOCK_HASH).unwrap();
+    height_to_block_hash.remove(&0).unwrap();
+    height_to_block_hash.remove(&2).unwrap();
+    height_to_block_hash.remove(&3).unwrap();
+    drop(height_to_block_hash);
+    wtx.commit().unwrap();
+
+    assert_eq!(context.index.gaps(0, 6).unwrap(), vec![(0, 1), (2, 4)]);
+    assert_eq!(context.index.gaps(3, 6).unwrap(), vec![(3, 4)]);
+    assert_eq!(context.index.gaps(4, 6).unwrap(), Vec::new());
+  }
 
   #[test]
-  fn height_limit() {
-    {
-      let context = Context::builder().args(["--height-limit", "0"]).build();
-      context.mine_blocks(1);
-      assert_eq!(context.index.block_height().unwrap(), None);
-      assert_eq!(context.index.block_count().unwrap(), 0);
-    }
+  fn verify_chain_continuity() {
+    let context = Context::builder().build();
 
-    {
-      let context = Context::builder().args(["--height-limit", "1"]).build();
-      context.mine_blocks(1);
-      assert_eq!(context.index.block_height().unwrap(), Some(Height(0)));
-      assert_eq!(context.index.block_count().unwrap(), 1);
-    }
+    context.mine_blocks(3);
 
-    {
-      let context = Context::builder().args(["--height-limit", "2"]).build();
-      context.mine_blocks(2);
-      assert_eq!(context.index.block_height().unwrap(), Some(Height(1)));
-      assert_eq!(context.index.block_count().unwrap(), 2);
-    }
+    context.index.verify_chain_continuity(0, 4).unwrap();
+    context.index.verify_chain_continuity(1, 3).unwrap();
+
+    let genesis = context.index.block_hash(Some(0)).unwrap().unwrap();
+    let wtx = context.index.begin_write().unwrap();
+    wtx
+      .open_table(HEIGHT_TO_BLOCK_HASH)
+      .unwrap()
+      .insert(&2, &genesis.store())
+      .unwrap();
+    wtx.commit().unwrap();
+
+    context.index.verify_chain_continuity(0, 2).unwrap();
+    context.index.verify_chain_continuity(3, 4).unwrap();
+
+    assert_eq!(
+      context
+        .index
+        .verify_chain_continuity(0, 4)
+        .unwrap_err()
+        .to_string(),
+      format!(
+        "chain broken at height 2: block {genesis} has previous block {}, but index has {} at height 1",
+        BlockHash::all_zeros(),
+        context.index.block_hash(Some(1)).unwrap().unwrap(),
+      )
+    );
   }
 
   #[test]
@@ -1608,6 +2886,35 @@ mod tests {
     );
   }
 
+  #[test]
+  fn list_adjacent_split_ranges_are_merged() {
+    let context = Context::builder().arg("--index-sats").build();
+
+    context.mine_blocks(1);
+
+    context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, Default::default())],
+      outputs: 2,
+      fee: 0,
+      ..Default::default()
+    });
+
+    context.mine_blocks(1);
+
+    let txid = context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 1, 0, Default::default()), (2, 1, 1, Default::default())],
+      fee: 0,
+      ..Default::default()
+    });
+
+    context.mine_blocks(1);
+
+    assert_eq!(
+      context.index.list(OutPoint::new(txid, 0)).unwrap().unwrap(),
+      List::Unspent(vec![(50 * COIN_VALUE, 100 * COIN_VALUE)])
+    );
+  }
+
   #[test]
   fn list_fee_paying_transaction_range() {
     let context = Context::builder().arg("--index-sats").build();
@@ -1848,6 +3155,123 @@ mod tests {
     }
   }
 
+  #[test]
+  fn index_survives_cookie_file_changes() {
+    let context = Context::builder().build();
+
+    let cookie_file = context.tempdir.path().join("cookie");
+
+    let client = context.index.client.get();
+
+    fs::write(cookie_file, "username:new-password").unwrap();
+
+    let start = Instant::now();
+
+    while Arc::ptr_eq(&client, &context.index.client.get()) {
+      assert!(
+        start.elapsed() < Duration::from_secs(10),
+        "client was not reloaded"
+      );
+      thread::sleep(Duration::from_millis(10));
+    }
+
+    context.mine_blocks(1);
+
+    assert_eq!(context.index.block_count().unwrap(), 2);
+  }
+
+  #[test]
+  fn block_events_are_sent_after_commit() {
+    let context = Context::builder().build();
+
+    let mut receiver = context.index.subscribe_block_events();
+
+    let blocks = context.mine_blocks(1);
+
+    context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      ..Default::default()
+    });
+
+    let blocks = blocks
+      .into_iter()
+      .chain(context.mine_blocks(1))
+      .collect::<Vec<Block>>();
+
+    assert_eq!(
+      receiver.try_recv().unwrap(),
+      BlockEvent {
+        block_hash: blocks[0].block_hash(),
+        height: 1,
+        inscriptions_added: 0,
+        ordinals_mined: 50 * COIN_VALUE,
+      }
+    );
+
+    assert_eq!(
+      receiver.try_recv().unwrap(),
+      BlockEvent {
+        block_hash: blocks[1].block_hash(),
+        height: 2,
+        inscriptions_added: 1,
+        ordinals_mined: 50 * COIN_VALUE,
+      }
+    );
+
+    assert!(receiver.try_recv().is_err());
+  }
+
+  #[test]
+  fn blocks_since_last_inscription() {
+    let context = Context::builder().build();
+
+    assert_eq!(context.index.blocks_since_last_inscription().unwrap(), None);
+
+    context.mine_blocks(1);
+
+    context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      ..Default::default()
+    });
+
+    context.mine_blocks(1);
+
+    assert_eq!(
+      context.index.blocks_since_last_inscription().unwrap(),
+      Some(0)
+    );
+
+    context.mine_blocks(3);
+
+    assert_eq!(
+      context.index.blocks_since_last_inscription().unwrap(),
+      Some(3)
+    );
+  }
+
+  #[test]
+  fn inscription_count() {
+    let context = Context::builder().build();
+
+    context.mine_blocks(2);
+
+    assert_eq!(context.index.inscription_count().unwrap(), 0);
+
+    context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      ..Default::default()
+    });
+
+    context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 0, 0, inscription("text/plain", "world").to_witness())],
+      ..Default::default()
+    });
+
+    context.mine_blocks(1);
+
+    assert_eq!(context.index.inscription_count().unwrap(), 2);
+  }
+
   #[test]
   fn inscriptions_without_sats_are_unbound() {
     for context in Context::configurations() {
@@ -2200,6 +3624,54 @@ mod tests {
     }
   }
 
+  #[test]
+  fn output_value_below_first_inscription_height() {
+    let context = Context::builder()
+      .args(["--index-sats", "--first-inscription-height", "10"])
+      .build();
+
+    let coinbase = context.mine_blocks(1)[0].txdata[0].txid();
+
+    assert_eq!(
+      context
+        .index
+        .get_output_value(OutPoint::new(coinbase, 0))
+        .unwrap(),
+      Some(50 * COIN_VALUE)
+    );
+  }
+
+  #[test]
+  fn output_value_is_none_for_lost_sats() {
+    let context = Context::builder().arg("--index-sats").build();
+    let coinbase = context.mine_blocks(1)[0].txdata[0].txid();
+
+    assert_eq!(
+      context
+        .index
+        .get_output_value(OutPoint::new(coinbase, 0))
+        .unwrap(),
+      Some(50 * COIN_VALUE)
+    );
+
+    context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, Default::default())],
+      fee: 50 * COIN_VALUE,
+      ..Default::default()
+    });
+
+    context.mine_blocks_with_subsidy(1, 0);
+
+    let satpoint = context.index.find(50 * COIN_VALUE).unwrap().unwrap();
+
+    assert_eq!(satpoint.outpoint, OutPoint::null());
+
+    assert_eq!(
+      context.index.get_output_value(satpoint.outpoint).unwrap(),
+      None
+    );
+  }
+
   #[test]
   fn lost_inscriptions() {
     for context in Context::configurations() {
@@ -2465,6 +3937,71 @@ mod tests {
     );
   }
 
+  #[test]
+  fn rare_sat_history_is_tracked() {
+    let context = Context::builder().arg("--index-sats").build();
+    let coinbase = context.mine_blocks(1)[0].txdata[0].txid();
+
+    assert_eq!(
+      context
+        .index
+        .rare_sat_history(Sat(50 * COIN_VALUE))
+        .unwrap()
+        .unwrap(),
+      [SatPoint {
+        outpoint: OutPoint::new(coinbase, 0),
+        offset: 0,
+      }],
+    );
+
+    let txid = context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, Default::default())],
+      ..Default::default()
+    });
+
+    context.mine_blocks(1);
+
+    assert_eq!(
+      context
+        .index
+        .rare_sat_history(Sat(50 * COIN_VALUE))
+        .unwrap()
+        .unwrap(),
+      [
+        SatPoint {
+          outpoint: OutPoint::new(coinbase, 0),
+          offset: 0,
+        },
+        SatPoint {
+          outpoint: OutPoint::new(txid, 0),
+          offset: 0,
+        },
+      ],
+    );
+
+    assert_eq!(context.index.rare_sat_history(Sat(1)).unwrap(), None);
+  }
+
+  #[test]
+  fn decode_sat_ranges() {
+    assert_eq!(super::decode_sat_ranges(&[]).unwrap(), []);
+    assert_eq!(
+      super::decode_sat_ranges(
+        &[
+          Lot::from((0, 1)).store(),
+          Lot::from((5 * COIN_VALUE, 10 * COIN_VALUE)).store(),
+        ]
+        .concat()
+      )
+      .unwrap(),
+      [(0, 1), (5 * COIN_VALUE, 10 * COIN_VALUE)],
+    );
+    assert_eq!(
+      super::decode_sat_ranges(&[0; 12]).unwrap_err().to_string(),
+      "sat range bytes must be a multiple of 11 bytes long, got 12"
+    );
+  }
+
   #[test]
   fn old_schema_gives_correct_error() {
     let tempdir = {
@@ -2519,6 +4056,166 @@ mod tests {
       format!("index at `{}{delimiter}regtest{delimiter}index.redb` appears to have been built with a newer, incompatible version of ord, consider updating ord: index schema {}, ord schema {SCHEMA_VERSION}", path.display(), u64::MAX));
   }
 
+  #[test]
+  fn schema_15_is_migrated() {
+    let tempdir = {
+      let context = Context::builder().arg("--index-sats").build();
+
+      context.mine_blocks(2);
+
+      let wtx = context.index.database.begin_write().unwrap();
+
+      wtx.delete_table(SAT_TO_RARITY).unwrap();
+
+      wtx
+        .open_table(STATISTIC_TO_COUNT)
+        .unwrap()
+        .insert(&Statistic::Schema.key(), &15)
+        .unwrap();
+
+      wtx.commit().unwrap();
+
+      context.tempdir
+    };
+
+    let context = Context::builder().tempdir(tempdir).build();
+
+    assert_eq!(context.index.statistic(Statistic::Schema), SCHEMA_VERSION);
+
+    assert_eq!(
+      context
+        .index
+        .database
+        .begin_read()
+        .unwrap()
+        .open_table(SAT_TO_RARITY)
+        .unwrap()
+        .iter()
+        .unwrap()
+        .map(|result| {
+          let (sat, rarity) = result.unwrap();
+          (sat.value(), rarity.value())
+        })
+        .collect::<Vec<(u64, u8)>>(),
+      [
+        (0, u8::from(Rarity::Mythic)),
+        (50 * COIN_VALUE, u8::from(Rarity::Uncommon)),
+        (100 * COIN_VALUE, u8::from(Rarity::Uncommon)),
+      ]
+    );
+  }
+
+  #[test]
+  fn schema_17_is_migrated() {
+    let tempdir = {
+      let context = Context::builder().build();
+
+      context.mine_blocks(2);
+
+      let wtx = context.index.database.begin_write().unwrap();
+
+      wtx.delete_table(HEIGHT_TO_HASH_CHECKSUM).unwrap();
+
+      wtx
+        .open_table(STATISTIC_TO_COUNT)
+        .unwrap()
+        .insert(&Statistic::Schema.key(), &17)
+        .unwrap();
+
+      wtx.commit().unwrap();
+
+      context.tempdir
+    };
+
+    let context = Context::builder().tempdir(tempdir).build();
+
+    assert_eq!(context.index.statistic(Statistic::Schema), SCHEMA_VERSION);
+
+    assert_eq!(
+      context
+        .index
+        .database
+        .begin_read()
+        .unwrap()
+        .open_table(HEIGHT_TO_HASH_CHECKSUM)
+        .unwrap()
+        .len()
+        .unwrap(),
+      3
+    );
+
+    assert_eq!(
+      context.index.verify_checksums(0, 3).unwrap(),
+      Vec::<u64>::new()
+    );
+  }
+
+  #[test]
+  fn verify_checksums() {
+    let context = Context::builder().build();
+
+    context.mine_blocks(2);
+
+    assert_eq!(
+      context.index.verify_checksums(0, 3).unwrap(),
+      Vec::<u64>::new()
+    );
+
+    let wtx = context.index.database.begin_write().unwrap();
+
+    wtx
+      .open_table(HEIGHT_TO_HASH_CHECKSUM)
+      .unwrap()
+      .insert(&1, &0)
+      .unwrap();
+
+    wtx.commit().unwrap();
+
+    assert_eq!(context.index.verify_checksums(0, 3).unwrap(), [1]);
+    assert_eq!(
+      context.index.verify_checksums(2, 3).unwrap(),
+      Vec::<u64>::new()
+    );
+  }
+
+  #[test]
+  fn schema_16_with_inscriptions_is_not_migrated() {
+    let tempdir = {
+      let context = Context::builder().build();
+
+      context.mine_blocks(1);
+
+      context.rpc_server.broadcast_tx(TransactionTemplate {
+        inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+        ..Default::default()
+      });
+
+      context.mine_blocks(1);
+
+      let wtx = context.index.database.begin_write().unwrap();
+
+      wtx
+        .open_table(STATISTIC_TO_COUNT)
+        .unwrap()
+        .insert(&Statistic::Schema.key(), &16)
+        .unwrap();
+
+      wtx.commit().unwrap();
+
+      context.tempdir
+    };
+
+    assert_eq!(
+      Context::builder()
+        .tempdir(tempdir)
+        .try_build()
+        .err()
+        .unwrap()
+        .to_string(),
+      "index contains inscriptions without content hashes and must be rebuilt"
+    );
+  }
+
   #[test]
   fn inscriptions_on_output() {
     for context in Context::configurations() {
@@ -2730,6 +4427,148 @@ mod tests {
     }
   }
 
+  #[test]
+  fn get_inscriptions_paginated() {
+    let context = Context::builder().build();
+
+    context.mine_blocks(1);
+
+    let mut ids = Vec::new();
+
+    for i in 0..5 {
+      let txid = context.rpc_server.broadcast_tx(TransactionTemplate {
+        inputs: &[(i + 1, 0, 0, inscription("text/plain", "hello").to_witness())],
+        ..Default::default()
+      });
+      ids.push(InscriptionId { txid, index: 0 });
+      context.mine_blocks(1);
+    }
+
+    assert_eq!(
+      context.index.get_inscriptions_paginated(0, 2).unwrap(),
+      (ids[0..2].to_vec(), 5, Some(2))
+    );
+
+    assert_eq!(
+      context.index.get_inscriptions_paginated(2, 2).unwrap(),
+      (ids[2..4].to_vec(), 5, Some(4))
+    );
+
+    assert_eq!(
+      context.index.get_inscriptions_paginated(4, 2).unwrap(),
+      (ids[4..].to_vec(), 5, None)
+    );
+
+    assert_eq!(
+      context.index.get_inscriptions_paginated(6, 2).unwrap(),
+      (Vec::new(), 5, None)
+    );
+  }
+
+  #[test]
+  fn inscriptions_in_transaction() {
+    let context = Context::builder().build();
+
+    context.mine_blocks(4);
+
+    let witness = inscription("text/plain", "hello").to_witness();
+
+    let txid = context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[
+        (1, 0, 0, witness.clone()),
+        (2, 0, 0, Default::default()),
+        (3, 0, 0, witness),
+      ],
+      ..Default::default()
+    });
+
+    let other = context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(4, 0, 0, inscription("text/plain", "world").to_witness())],
+      ..Default::default()
+    });
+
+    context.mine_blocks(1);
+
+    assert_eq!(
+      context.index.inscriptions_in_transaction(txid).unwrap(),
+      [
+        InscriptionId { txid, index: 0 },
+        InscriptionId { txid, index: 1 },
+      ]
+    );
+
+    assert_eq!(
+      context.index.inscriptions_in_transaction(other).unwrap(),
+      [InscriptionId {
+        txid: other,
+        index: 0
+      }]
+    );
+
+    assert_eq!(
+      context
+        .index
+        .inscriptions_in_transaction(context.mine_blocks(1)[0].txdata[0].txid())
+        .unwrap(),
+      []
+    );
+  }
+
+  #[test]
+  fn inscriptions_with_content_hash() {
+    let context = Context::builder().build();
+
+    context.mine_blocks(3);
+
+    let first = context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      ..Default::default()
+    });
+    context.mine_blocks(1);
+
+    let second = context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 0, 0, inscription("image/png", "hello").to_witness())],
+      ..Default::default()
+    });
+    context.mine_blocks(1);
+
+    context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(3, 0, 0, inscription("text/plain", "world").to_witness())],
+      ..Default::default()
+    });
+    context.mine_blocks(1);
+
+    let inscriptions = context
+      .index
+      .inscriptions_with_content_hash(bitcoin::hashes::sha256::Hash::hash(b"hello").to_byte_array())
+      .unwrap()
+      .into_iter()
+      .collect::<HashSet<InscriptionId>>();
+
+    let expected = [
+      InscriptionId {
+        txid: first,
+        index: 0,
+      },
+      InscriptionId {
+        txid: second,
+        index: 0,
+      },
+    ]
+    .into_iter()
+    .collect::<HashSet<InscriptionId>>();
+
+    assert_eq!(inscriptions, expected);
+
+    assert_eq!(
+      context
+        .index
+        .inscriptions_with_content_hash([0; 32])
+        .unwrap(),
+      Vec::new()
+    );
+  }
+
   #[test]
   fn unsynced_index_fails() {
     for context in Context::configurations() {
@@ -3690,6 +5529,38 @@ mod tests {
     }
   }
 
+  #[test]
+  fn compact_recreates_savepoint() {
+    let mut context = Context::builder().build();
+
+    context.index.set_durability(redb::Durability::Immediate);
+
+    context.mine_blocks(9);
+
+    let savepoints = |index: &Index| {
+      index
+        .begin_write()
+        .unwrap()
+        .list_persistent_savepoints()
+        .unwrap()
+        .count()
+    };
+
+    assert!(savepoints(&context.index) > 0);
+
+    let commits = context.index.statistic(Statistic::Commits);
+
+    context.index.compact().unwrap();
+
+    assert_eq!(savepoints(&context.index), 1);
+
+    assert_eq!(context.index.statistic(Statistic::Commits), commits);
+
+    context.mine_blocks(1);
+
+    assert_eq!(context.index.block_count().unwrap(), 11);
+  }
+
   #[test]
   fn recover_from_reorg() {
     for mut context in Context::configurations() {
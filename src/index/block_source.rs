@@ -0,0 +1,720 @@
+use {
+  super::{updater::BlockData, *},
+  std::{
+    collections::VecDeque,
+    fs::File,
+    io::{Seek, SeekFrom},
+    sync::mpsc,
+  },
+};
+
+pub(crate) trait BlockSource: Send {
+  fn get_block(&self, height: u64) -> Result<Option<Block>>;
+}
+
+/// Fetches blocks starting at `height` on a background thread, trying each
+/// source in order, and buffers up to `prefetch_blocks` blocks ahead of the
+/// receiver. The channel closes at the chain tip, at `height_limit`, or on
+/// error.
+pub(crate) fn prefetch(
+  block_sources: Vec<Box<dyn BlockSource>>,
+  mut height: u64,
+  height_limit: Option<u64>,
+  prefetch_blocks: usize,
+) -> mpsc::Receiver<BlockData> {
+  let (tx, rx) = mpsc::sync_channel(prefetch_blocks);
+
+  thread::spawn(move || loop {
+    if let Some(height_limit) = height_limit {
+      if height >= height_limit {
+        break;
+      }
+    }
+
+    match block_sources
+      .iter()
+      .find_map(|block_source| block_source.get_block(height).transpose())
+      .transpose()
+    {
+      Ok(Some(block)) => {
+        if let Err(err) = tx.send(block.into()) {
+          log::info!("Block receiver disconnected: {err}");
+          break;
+        }
+        height += 1;
+      }
+      Ok(None) => break,
+      Err(err) => {
+        log::error!("failed to fetch block {height}: {err}");
+        break;
+      }
+    }
+  });
+
+  rx
+}
+
+/// A least-recently-used cache of fetched blocks, keyed by hash, holding at
+/// most `capacity` bytes of serialized block data. It is shared by every
+/// updater run, so blocks fetched before a reorg aren't fetched again while
+/// recovering from it.
+pub(crate) struct BlockCache {
+  capacity: usize,
+  state: Mutex<BlockCacheState>,
+}
+
+#[derive(Default)]
+struct BlockCacheState {
+  blocks: HashMap<BlockHash, (Block, usize)>,
+  order: VecDeque<BlockHash>,
+  size: usize,
+}
+
+impl BlockCache {
+  pub(crate) fn new(capacity: usize) -> Self {
+    Self {
+      capacity,
+      state: Mutex::new(BlockCacheState::default()),
+    }
+  }
+
+  pub(crate) fn get(&self, hash: BlockHash) -> Option<Block> {
+    let mut state = self.state.lock().unwrap();
+
+    let block = state.blocks.get(&hash)?.0.clone();
+
+    state.order.retain(|cached| *cached != hash);
+    state.order.push_back(hash);
+
+    Some(block)
+  }
+
+  pub(crate) fn insert(&self, hash: BlockHash, block: Block) {
+    let size = block.size();
+
+    if size > self.capacity {
+      return;
+    }
+
+    let mut state = self.state.lock().unwrap();
+
+    if let Some((_, size)) = state.blocks.remove(&hash) {
+      state.size -= size;
+      state.order.retain(|cached| *cached != hash);
+    }
+
+    while state.size + size > self.capacity {
+      let Some(evicted) = state.order.pop_front() else {
+        break;
+      };
+
+      if let Some((_, size)) = state.blocks.remove(&evicted) {
+        state.size -= size;
+      }
+    }
+
+    state.size += size;
+    state.order.push_back(hash);
+    state.blocks.insert(hash, (block, size));
+  }
+}
+
+pub(crate) struct RpcBlockSource {
+  pub(crate) block_cache: Arc<BlockCache>,
+  pub(crate) client: ReloadableClient,
+  pub(crate) first_inscription_height: u64,
+  pub(crate) full_blocks: bool,
+  pub(crate) max_retries: u32,
+}
+
+impl BlockSource for RpcBlockSource {
+  fn get_block(&self, height: u64) -> Result<Option<Block>> {
+    let mut errors = 0;
+    loop {
+      let client = self.client.get();
+
+      match client
+        .get_block_hash(height)
+        .into_option()
+        .and_then(|option| {
+          option
+            .map(|hash| {
+              if let Some(block) = self.block_cache.get(hash) {
+                return Ok(block);
+              }
+
+              let block = if self.full_blocks || height >= self.first_inscription_height {
+                client.get_block(&hash)?
+              } else {
+                Block {
+                  header: client.get_block_header(&hash)?,
+                  txdata: Vec::new(),
+                }
+              };
+
+              self.block_cache.insert(hash, block.clone());
+
+              Ok(block)
+            })
+            .transpose()
+        }) {
+        Err(err) => {
+          if cfg!(test) {
+            return Err(err);
+          }
+
+          errors += 1;
+
+          if errors > self.max_retries {
+            log::error!(
+              "failed to fetch block {height} after {} retries, giving up",
+              self.max_retries
+            );
+            return Err(err);
+          }
+
+          let seconds = 2u64.saturating_pow(errors).min(120);
+          log::warn!("failed to fetch block {height}, retrying in {seconds}s: {err}");
+
+          thread::sleep(Duration::from_secs(seconds));
+        }
+        Ok(result) => return Ok(result),
+      }
+    }
+  }
+}
+
+#[derive(Debug)]
+struct BlockLocation {
+  hash: BlockHash,
+  file: usize,
+  offset: u64,
+  size: usize,
+}
+
+/// Reads blocks from the `blkNNNNN.dat` files in a Bitcoin Core blocks
+/// directory. Blocks are stored in the order they were received, so heights
+/// are assigned by following `prev_blockhash` links from the genesis block to
+/// the highest block found. Block files also contain stale blocks, so a block
+/// is only returned if its hash matches the one expected at its height.
+#[derive(Debug)]
+pub(crate) struct FileBlockSource {
+  blocks: Vec<BlockLocation>,
+  files: Vec<PathBuf>,
+  xor_key: Option<[u8; 8]>,
+}
+
+impl FileBlockSource {
+  pub(crate) fn open(dir: &Path, chain: Chain) -> Result<Self> {
+    let mut files = fs::read_dir(dir)
+      .with_context(|| format!("failed to read block files from `{}`", dir.display()))?
+      .map(|entry| Ok(entry?.path()))
+      .collect::<Result<Vec<PathBuf>>>()?
+      .into_iter()
+      .filter(|path| {
+        path
+          .file_name()
+          .and_then(|name| name.to_str())
+          .map(|name| name.starts_with("blk") && name.ends_with(".dat"))
+          .unwrap_or_default()
+      })
+      .collect::<Vec<PathBuf>>();
+
+    files.sort();
+
+    let xor_key = match fs::read(dir.join("xor.dat")) {
+      Ok(key) => {
+        let key: [u8; 8] = key
+          .try_into()
+          .map_err(|key: Vec<u8>| anyhow!("xor.dat must be 8 bytes long, got {}", key.len()))?;
+        (key != [0; 8]).then_some(key)
+      }
+      Err(err) if err.kind() == io::ErrorKind::NotFound => None,
+      Err(err) => return Err(err.into()),
+    };
+
+    let mut source = Self {
+      blocks: Vec::new(),
+      files,
+      xor_key,
+    };
+
+    let magic = chain.network().magic().to_bytes();
+
+    let mut headers = HashMap::new();
+    let mut children = HashMap::<BlockHash, Vec<BlockHash>>::new();
+
+    for (file, path) in source.files.iter().enumerate() {
+      let mut reader = File::open(path)?;
+      let len = reader.metadata()?.len();
+      let mut offset = 0;
+
+      while offset + 88 <= len {
+        let mut prefix = [0; 88];
+        source.read_at(&mut reader, offset, &mut prefix)?;
+
+        if prefix[..4] != magic {
+          break;
+        }
+
+        let size = u32::from_le_bytes(prefix[4..8].try_into().unwrap());
+
+        if offset + 8 + u64::from(size) > len {
+          break;
+        }
+
+        let header: Header = consensus::encode::deserialize(&prefix[8..])?;
+        let hash = header.block_hash();
+
+        children
+          .entry(header.prev_blockhash)
+          .or_default()
+          .push(hash);
+
+        headers.insert(
+          hash,
+          (
+            header.prev_blockhash,
+            BlockLocation {
+              hash,
+              file,
+              offset: offset + 8,
+              size: size.try_into().unwrap(),
+            },
+          ),
+        );
+
+        offset += 8 + u64::from(size);
+      }
+    }
+
+    let genesis = chain.genesis_block().block_hash();
+
+    if !headers.contains_key(&genesis) {
+      return Ok(source);
+    }
+
+    let mut tip = (0, genesis);
+    let mut queue = VecDeque::from([(0, genesis)]);
+    while let Some((height, hash)) = queue.pop_front() {
+      if height > tip.0 {
+        tip = (height, hash);
+      }
+      for child in children.get(&hash).into_iter().flatten() {
+        queue.push_back((height + 1, *child));
+      }
+    }
+
+    let mut hash = tip.1;
+    loop {
+      let (prev_blockhash, location) = headers.remove(&hash).unwrap();
+      source.blocks.push(location);
+      if hash == genesis {
+        break;
+      }
+      hash = prev_blockhash;
+    }
+
+    source.blocks.reverse();
+
+    log::info!(
+      "Found {} blocks in {} block files",
+      source.blocks.len(),
+      source.files.len()
+    );
+
+    Ok(source)
+  }
+
+  fn read_at(&self, file: &mut File, offset: u64, buffer: &mut [u8]) -> Result {
+    file.seek(SeekFrom::Start(offset))?;
+    file.read_exact(buffer)?;
+
+    if let Some(key) = self.xor_key {
+      for (i, byte) in buffer.iter_mut().enumerate() {
+        *byte ^= key[usize::try_from((offset + i as u64) % 8).unwrap()];
+      }
+    }
+
+    Ok(())
+  }
+}
+
+impl FileBlockSource {
+  pub(crate) fn get_block(&self, height: u64, hash: BlockHash) -> Result<Option<Block>> {
+    let Some(location) = self.blocks.get(usize::try_from(height)?) else {
+      return Ok(None);
+    };
+
+    if location.hash != hash {
+      return Ok(None);
+    }
+
+    let mut buffer = vec![0; location.size];
+
+    self.read_at(
+      &mut File::open(&self.files[location.file])?,
+      location.offset,
+      &mut buffer,
+    )?;
+
+    Ok(Some(consensus::encode::deserialize(&buffer)?))
+  }
+}
+
+/// Block files are only scanned the first time a block is requested from them,
+/// so opening an index, which every command does, doesn't read them all.
+pub(crate) struct LazyFileBlockSource {
+  chain: Chain,
+  dir: PathBuf,
+  source: Mutex<Option<Arc<FileBlockSource>>>,
+}
+
+impl LazyFileBlockSource {
+  pub(crate) fn new(dir: PathBuf, chain: Chain) -> Self {
+    Self {
+      chain,
+      dir,
+      source: Mutex::new(None),
+    }
+  }
+
+  fn get(&self) -> Result<Arc<FileBlockSource>> {
+    let mut source = self.source.lock().unwrap();
+
+    if let Some(source) = source.as_ref() {
+      return Ok(source.clone());
+    }
+
+    let opened = Arc::new(FileBlockSource::open(&self.dir, self.chain)?);
+
+    *source = Some(opened.clone());
+
+    Ok(opened)
+  }
+}
+
+/// Serves blocks from block files that are on Bitcoin Core's active chain.
+/// Blocks that aren't, and heights Bitcoin Core can't be asked about, are left
+/// to later sources.
+pub(crate) struct ActiveChainFileBlockSource {
+  pub(crate) block_files: Arc<LazyFileBlockSource>,
+  pub(crate) client: ReloadableClient,
+}
+
+impl BlockSource for ActiveChainFileBlockSource {
+  fn get_block(&self, height: u64) -> Result<Option<Block>> {
+    let Ok(Some(hash)) = self.client.get().get_block_hash(height).into_option() else {
+      return Ok(None);
+    };
+
+    self.block_files.get()?.get_block(height, hash)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {
+    super::*,
+    bitcoin::{block::Version, hash_types::TxMerkleNode, CompactTarget},
+    std::sync::atomic::AtomicU64,
+    tempfile::TempDir,
+  };
+
+  fn block(prev_blockhash: BlockHash, nonce: u32) -> Block {
+    Block {
+      header: Header {
+        version: Version::ONE,
+        prev_blockhash,
+        merkle_root: TxMerkleNode::all_zeros(),
+        time: 0,
+        bits: CompactTarget::from_consensus(0),
+        nonce,
+      },
+      txdata: Vec::new(),
+    }
+  }
+
+  struct MockBlockSource {
+    blocks: Vec<Block>,
+    reached: Option<(u64, mpsc::SyncSender<()>)>,
+    requests: Arc<AtomicU64>,
+  }
+
+  impl BlockSource for MockBlockSource {
+    fn get_block(&self, height: u64) -> Result<Option<Block>> {
+      self.requests.fetch_add(1, atomic::Ordering::Relaxed);
+
+      if let Some((reached, tx)) = &self.reached {
+        if height == *reached {
+          tx.send(()).unwrap();
+        }
+      }
+
+      Ok(self.blocks.get(usize::try_from(height)?).cloned())
+    }
+  }
+
+  fn chain(len: u32) -> Vec<Block> {
+    let mut blocks = vec![Chain::Regtest.genesis_block()];
+
+    for nonce in 1..len {
+      blocks.push(block(blocks.last().unwrap().block_hash(), nonce));
+    }
+
+    blocks
+  }
+
+  fn mock(blocks: Vec<Block>) -> (Box<dyn BlockSource>, Arc<AtomicU64>) {
+    let requests = Arc::new(AtomicU64::new(0));
+    (
+      Box::new(MockBlockSource {
+        blocks,
+        reached: None,
+        requests: requests.clone(),
+      }),
+      requests,
+    )
+  }
+
+  fn headers(rx: mpsc::Receiver<BlockData>) -> Vec<Header> {
+    rx.iter().map(|block| block.header).collect()
+  }
+
+  #[test]
+  fn prefetch_stops_at_chain_tip() {
+    let blocks = chain(5);
+    let (source, _) = mock(blocks.clone());
+
+    assert_eq!(
+      headers(prefetch(vec![source], 2, None, 2)),
+      blocks[2..]
+        .iter()
+        .map(|block| block.header)
+        .collect::<Vec<Header>>(),
+    );
+  }
+
+  #[test]
+  fn prefetch_stops_at_height_limit() {
+    let blocks = chain(5);
+    let (source, requests) = mock(blocks.clone());
+
+    assert_eq!(
+      headers(prefetch(vec![source], 0, Some(3), 8)),
+      blocks[..3]
+        .iter()
+        .map(|block| block.header)
+        .collect::<Vec<Header>>(),
+    );
+
+    assert_eq!(requests.load(atomic::Ordering::Relaxed), 3);
+  }
+
+  #[test]
+  fn prefetch_falls_back_to_later_sources() {
+    let blocks = chain(4);
+    let (files, _) = mock(blocks[..2].to_vec());
+    let (rpc, rpc_requests) = mock(blocks.clone());
+
+    assert_eq!(
+      headers(prefetch(vec![files, rpc], 0, None, 8)),
+      blocks
+        .iter()
+        .map(|block| block.header)
+        .collect::<Vec<Header>>(),
+    );
+
+    assert_eq!(rpc_requests.load(atomic::Ordering::Relaxed), 3);
+  }
+
+  #[test]
+  fn prefetch_is_bounded() {
+    let requests = Arc::new(AtomicU64::new(0));
+    let (reached_tx, reached_rx) = mpsc::sync_channel(0);
+
+    let source = Box::new(MockBlockSource {
+      blocks: chain(10),
+      reached: Some((2, reached_tx)),
+      requests: requests.clone(),
+    });
+
+    let rx = prefetch(vec![source], 0, None, 2);
+
+    // blocks 0 and 1 fill the channel, so block 2 can't be sent, and no
+    // further block requested, until a block is received
+    reached_rx.recv().unwrap();
+
+    assert_eq!(requests.load(atomic::Ordering::Relaxed), 3);
+
+    assert_eq!(headers(rx).len(), 10);
+  }
+
+  #[test]
+  fn block_cache_evicts_least_recently_used_block() {
+    let blocks = chain(3);
+    let hashes = blocks
+      .iter()
+      .map(Block::block_hash)
+      .collect::<Vec<BlockHash>>();
+
+    let cache = BlockCache::new(blocks[0].size() + blocks[1].size());
+
+    cache.insert(hashes[0], blocks[0].clone());
+    cache.insert(hashes[1], blocks[1].clone());
+
+    assert_eq!(cache.get(hashes[0]), Some(blocks[0].clone()));
+
+    cache.insert(hashes[2], blocks[2].clone());
+
+    assert_eq!(cache.get(hashes[0]), Some(blocks[0].clone()));
+    assert_eq!(cache.get(hashes[1]), None);
+    assert_eq!(cache.get(hashes[2]), Some(blocks[2].clone()));
+  }
+
+  #[test]
+  fn block_cache_skips_blocks_larger_than_capacity() {
+    let genesis = Chain::Regtest.genesis_block();
+
+    let cache = BlockCache::new(genesis.size() - 1);
+
+    cache.insert(genesis.block_hash(), genesis.clone());
+
+    assert_eq!(cache.get(genesis.block_hash()), None);
+  }
+
+  fn write_blocks(path: &Path, blocks: &[&Block], xor_key: [u8; 8]) {
+    let mut file = Vec::new();
+
+    for block in blocks {
+      let block = consensus::encode::serialize(block);
+      file.extend_from_slice(&Network::Regtest.magic().to_bytes());
+      file.extend_from_slice(&u32::try_from(block.len()).unwrap().to_le_bytes());
+      file.extend_from_slice(&block);
+    }
+
+    file.extend_from_slice(&[0; 16]);
+
+    for (i, byte) in file.iter_mut().enumerate() {
+      *byte ^= xor_key[i % 8];
+    }
+
+    fs::write(path, file).unwrap();
+  }
+
+  #[test]
+  fn blocks_are_ordered_by_height() {
+    let tempdir = TempDir::new().unwrap();
+
+    let genesis = Chain::Regtest.genesis_block();
+    let one = block(genesis.block_hash(), 1);
+    let two = block(one.block_hash(), 2);
+    let orphan = block(one.block_hash(), 3);
+
+    write_blocks(
+      &tempdir.path().join("blk00000.dat"),
+      &[&genesis, &two],
+      [0; 8],
+    );
+    write_blocks(
+      &tempdir.path().join("blk00001.dat"),
+      &[&orphan, &one],
+      [0; 8],
+    );
+    fs::write(tempdir.path().join("rev00000.dat"), [1, 2, 3]).unwrap();
+
+    let source = FileBlockSource::open(tempdir.path(), Chain::Regtest).unwrap();
+
+    assert_eq!(
+      source.get_block(0, genesis.block_hash()).unwrap(),
+      Some(genesis)
+    );
+    assert_eq!(source.get_block(1, one.block_hash()).unwrap(), Some(one));
+    assert_eq!(source.get_block(2, two.block_hash()).unwrap(), Some(two));
+    assert_eq!(source.get_block(3, BlockHash::all_zeros()).unwrap(), None);
+  }
+
+  #[test]
+  fn blocks_not_on_active_chain_are_not_returned() {
+    let tempdir = TempDir::new().unwrap();
+
+    let genesis = Chain::Regtest.genesis_block();
+    let one = block(genesis.block_hash(), 1);
+    let stale = block(one.block_hash(), 2);
+    let active = block(one.block_hash(), 3);
+
+    write_blocks(
+      &tempdir.path().join("blk00000.dat"),
+      &[&genesis, &one, &stale, &active],
+      [0; 8],
+    );
+
+    let source = FileBlockSource::open(tempdir.path(), Chain::Regtest).unwrap();
+
+    assert_eq!(source.get_block(1, one.block_hash()).unwrap(), Some(one));
+    assert_eq!(source.get_block(2, active.block_hash()).unwrap(), None);
+  }
+
+  #[test]
+  fn obfuscated_block_files_are_read() {
+    let tempdir = TempDir::new().unwrap();
+
+    let key = [1, 2, 3, 4, 5, 6, 7, 8];
+
+    let genesis = Chain::Regtest.genesis_block();
+    let one = block(genesis.block_hash(), 1);
+
+    write_blocks(&tempdir.path().join("blk00000.dat"), &[&genesis, &one], key);
+    fs::write(tempdir.path().join("xor.dat"), key).unwrap();
+
+    let source = FileBlockSource::open(tempdir.path(), Chain::Regtest).unwrap();
+
+    assert_eq!(
+      source.get_block(0, genesis.block_hash()).unwrap(),
+      Some(genesis)
+    );
+    assert_eq!(source.get_block(1, one.block_hash()).unwrap(), Some(one));
+  }
+
+  #[test]
+  fn block_files_are_scanned_on_first_request() {
+    let tempdir = TempDir::new().unwrap();
+
+    let blocks_dir = tempdir.path().join("blocks");
+
+    let source = LazyFileBlockSource::new(blocks_dir.clone(), Chain::Regtest);
+
+    fs::create_dir(&blocks_dir).unwrap();
+
+    let genesis = Chain::Regtest.genesis_block();
+
+    write_blocks(&blocks_dir.join("blk00000.dat"), &[&genesis], [0; 8]);
+
+    assert_eq!(
+      source
+        .get()
+        .unwrap()
+        .get_block(0, genesis.block_hash())
+        .unwrap(),
+      Some(genesis)
+    );
+  }
+
+  #[test]
+  fn missing_genesis_block_yields_no_blocks() {
+    let tempdir = TempDir::new().unwrap();
+
+    let one = block(Chain::Regtest.genesis_block().block_hash(), 1);
+
+    write_blocks(&tempdir.path().join("blk00000.dat"), &[&one], [0; 8]);
+
+    let source = FileBlockSource::open(tempdir.path(), Chain::Regtest).unwrap();
+
+    assert_eq!(
+      source
+        .get_block(0, Chain::Regtest.genesis_block().block_hash())
+        .unwrap(),
+      None
+    );
+  }
+}
@@ -22,6 +22,22 @@ impl Entry for BlockHash {
   }
 }
 
+pub(super) type HeaderValue = [u8; 80];
+
+impl Entry for Header {
+  type Value = HeaderValue;
+
+  fn load(value: Self::Value) -> Self {
+    consensus::encode::deserialize(&value).unwrap()
+  }
+
+  fn store(self) -> Self::Value {
+    let mut buffer = Vec::new();
+    self.consensus_encode(&mut buffer).unwrap();
+    buffer.try_into().unwrap()
+  }
+}
+
 #[derive(Debug, PartialEq, Copy, Clone)]
 pub(crate) struct RuneEntry {
   pub(crate) burned: u128,
@@ -284,9 +300,27 @@ impl Entry for SatPoint {
   }
 }
 
-pub(super) type SatRange = (u64, u64);
+pub(super) type SatPointHistoryValue = [u8; 52];
+
+impl Entry for (u64, SatPoint) {
+  type Value = SatPointHistoryValue;
+
+  fn load(value: Self::Value) -> Self {
+    (
+      u64::from_be_bytes(value[..8].try_into().unwrap()),
+      SatPoint::load(value[8..].try_into().unwrap()),
+    )
+  }
 
-impl Entry for SatRange {
+  fn store(self) -> Self::Value {
+    let mut value = [0; 52];
+    value[..8].copy_from_slice(&self.0.to_be_bytes());
+    value[8..].copy_from_slice(&self.1.store());
+    value
+  }
+}
+
+impl Entry for Lot {
   type Value = [u8; 11];
 
   fn load([b0, b1, b2, b3, b4, b5, b6, b7, b8, b9, b10]: Self::Value) -> Self {
@@ -300,12 +334,15 @@ impl Entry for SatRange {
     // 33 bit delta
     let delta = raw_delta >> 3;
 
-    (base, base + delta)
+    Lot {
+      start: base,
+      end: base + delta,
+    }
   }
 
   fn store(self) -> Self::Value {
-    let base = self.0;
-    let delta = self.1 - self.0;
+    let base = self.start;
+    let delta = self.len();
     let n = u128::from(base) | u128::from(delta) << 51;
     n.to_le_bytes()[0..11].try_into().unwrap()
   }
@@ -507,4 +544,21 @@ mod tests {
       RuneId::load((1, 2)),
     );
   }
+
+  #[test]
+  fn lot_entry() {
+    let lot = Lot {
+      start: 2099999997689999,
+      end: 2099999997690000,
+    };
+
+    assert_eq!(Lot::load(lot.store()), lot);
+
+    let lot = Lot {
+      start: 0,
+      end: 50 * COIN_VALUE,
+    };
+
+    assert_eq!(Lot::load(lot.store()), lot);
+  }
 }
@@ -0,0 +1,193 @@
+use super::*;
+
+/// A contiguous range of sats, `start` inclusive and `end` exclusive.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) struct Lot {
+  pub(crate) start: u64,
+  pub(crate) end: u64,
+}
+
+impl Lot {
+  pub(crate) fn len(self) -> u64 {
+    self.end - self.start
+  }
+
+  pub(crate) fn contains(self, sat: u64) -> bool {
+    self.start <= sat && sat < self.end
+  }
+
+  pub(crate) fn split(self, at: u64) -> (Lot, Lot) {
+    assert!(self.start <= at && at <= self.end, "split outside of lot");
+
+    (
+      Lot {
+        start: self.start,
+        end: at,
+      },
+      Lot {
+        start: at,
+        end: self.end,
+      },
+    )
+  }
+
+  pub(crate) fn merge(self, other: Lot) -> Option<Lot> {
+    (self.end == other.start).then_some(Lot {
+      start: self.start,
+      end: other.end,
+    })
+  }
+}
+
+impl From<(u64, u64)> for Lot {
+  fn from((start, end): (u64, u64)) -> Self {
+    Self { start, end }
+  }
+}
+
+impl From<Lot> for (u64, u64) {
+  fn from(lot: Lot) -> Self {
+    (lot.start, lot.end)
+  }
+}
+
+/// Sat ranges waiting to be assigned, consumed from the front.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub(crate) struct LotQueue(VecDeque<Lot>);
+
+impl LotQueue {
+  /// Pops up to `amount` sats from the front of the queue. If the front lot
+  /// is larger than `amount`, it is split and the remainder is pushed back
+  /// onto the front and also returned.
+  pub(crate) fn consume(&mut self, amount: u64) -> Result<(Lot, Option<Lot>)> {
+    let lot = self
+      .0
+      .pop_front()
+      .ok_or_else(|| anyhow!("insufficient inputs for transaction outputs"))?;
+
+    if lot.len() > amount {
+      let (consumed, remainder) = lot.split(lot.start + amount);
+      self.0.push_front(remainder);
+      Ok((consumed, Some(remainder)))
+    } else {
+      Ok((lot, None))
+    }
+  }
+
+  /// Pushes `excess` onto the front of the queue, so it is consumed first.
+  pub(crate) fn extend_from_excess(&mut self, excess: Lot) {
+    self.0.push_front(excess);
+  }
+
+  pub(crate) fn push_back(&mut self, lot: Lot) {
+    self.0.push_back(lot);
+  }
+
+  pub(crate) fn is_empty(&self) -> bool {
+    self.0.is_empty()
+  }
+}
+
+impl Extend<Lot> for LotQueue {
+  fn extend<T: IntoIterator<Item = Lot>>(&mut self, iter: T) {
+    self.0.extend(iter);
+  }
+}
+
+impl FromIterator<Lot> for LotQueue {
+  fn from_iter<T: IntoIterator<Item = Lot>>(iter: T) -> Self {
+    Self(iter.into_iter().collect())
+  }
+}
+
+impl IntoIterator for LotQueue {
+  type Item = Lot;
+  type IntoIter = std::collections::vec_deque::IntoIter<Lot>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    self.0.into_iter()
+  }
+}
+
+impl<'a> IntoIterator for &'a LotQueue {
+  type Item = &'a Lot;
+  type IntoIter = std::collections::vec_deque::Iter<'a, Lot>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    self.0.iter()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn len() {
+    assert_eq!(Lot::from((0, 0)).len(), 0);
+    assert_eq!(Lot::from((5, 10)).len(), 5);
+  }
+
+  #[test]
+  fn contains() {
+    let lot = Lot::from((5, 10));
+    assert!(!lot.contains(4));
+    assert!(lot.contains(5));
+    assert!(lot.contains(9));
+    assert!(!lot.contains(10));
+  }
+
+  #[test]
+  fn split() {
+    assert_eq!(
+      Lot::from((5, 10)).split(7),
+      (Lot::from((5, 7)), Lot::from((7, 10)))
+    );
+    assert_eq!(
+      Lot::from((5, 10)).split(10),
+      (Lot::from((5, 10)), Lot::from((10, 10)))
+    );
+  }
+
+  #[test]
+  #[should_panic(expected = "split outside of lot")]
+  fn split_outside_of_lot() {
+    Lot::from((5, 10)).split(11);
+  }
+
+  #[test]
+  fn merge() {
+    assert_eq!(
+      Lot::from((5, 7)).merge(Lot::from((7, 10))),
+      Some(Lot::from((5, 10)))
+    );
+    assert_eq!(Lot::from((5, 7)).merge(Lot::from((8, 10))), None);
+    assert_eq!(Lot::from((7, 10)).merge(Lot::from((5, 7))), None);
+  }
+
+  #[test]
+  fn consume() {
+    let mut queue = [Lot::from((0, 5)), Lot::from((10, 15))]
+      .into_iter()
+      .collect::<LotQueue>();
+
+    assert_eq!(
+      queue.consume(3).unwrap(),
+      (Lot::from((0, 3)), Some(Lot::from((3, 5))))
+    );
+    assert_eq!(queue.consume(3).unwrap(), (Lot::from((3, 5)), None));
+    assert_eq!(queue.consume(5).unwrap(), (Lot::from((10, 15)), None));
+    assert_eq!(
+      queue.consume(1).unwrap_err().to_string(),
+      "insufficient inputs for transaction outputs"
+    );
+  }
+
+  #[test]
+  fn extend_from_excess() {
+    let mut queue = [Lot::from((10, 15))].into_iter().collect::<LotQueue>();
+    queue.extend_from_excess(Lot::from((0, 5)));
+    assert_eq!(queue.consume(5).unwrap(), (Lot::from((0, 5)), None));
+    assert_eq!(queue.consume(5).unwrap(), (Lot::from((10, 15)), None));
+  }
+}
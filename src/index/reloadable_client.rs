@@ -0,0 +1,78 @@
+use {
+  super::*,
+  std::sync::{RwLock, Weak},
+};
+
+/// A Bitcoin Core RPC client that can be replaced while it is shared. `get`
+/// clones the current client out from under the lock, so no lock is held
+/// across an RPC call and replacing the client never waits on one.
+#[derive(Clone)]
+pub(crate) struct ReloadableClient(Arc<RwLock<Arc<Client>>>);
+
+impl ReloadableClient {
+  pub(crate) fn new(client: Client) -> Self {
+    Self(Arc::new(RwLock::new(Arc::new(client))))
+  }
+
+  pub(crate) fn get(&self) -> Arc<Client> {
+    self.0.read().unwrap().clone()
+  }
+
+  /// Bitcoin Core writes a new cookie file every time it starts. Poll the
+  /// cookie file and replace the client when it changes, so a long-running
+  /// server keeps working across bitcoind restarts. Calls already holding the
+  /// old client finish with the old credentials.
+  pub(crate) fn watch_cookie_file(
+    &self,
+    rpc_url: String,
+    rpc_timeout: Option<Duration>,
+    cookie_file: PathBuf,
+  ) {
+    let client: Weak<RwLock<Arc<Client>>> = Arc::downgrade(&self.0);
+
+    let interval = if cfg!(test) {
+      Duration::from_millis(10)
+    } else {
+      Duration::from_secs(1)
+    };
+
+    let modified = |path: &Path| {
+      fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+    };
+
+    thread::spawn(move || {
+      let mut last_modified = modified(&cookie_file);
+
+      loop {
+        thread::sleep(interval);
+
+        let Some(client) = client.upgrade() else {
+          break;
+        };
+
+        let current = modified(&cookie_file);
+
+        if current == last_modified {
+          continue;
+        }
+
+        match Options::connect(&rpc_url, Auth::CookieFile(cookie_file.clone()), rpc_timeout) {
+          Ok(new) => {
+            log::info!(
+              "Reloaded credentials from cookie file at `{}`",
+              cookie_file.display()
+            );
+            *client.write().unwrap() = Arc::new(new);
+            last_modified = current;
+          }
+          Err(err) => log::warn!(
+            "failed to reload cookie file at `{}`: {err}",
+            cookie_file.display()
+          ),
+        }
+      }
+    });
+  }
+}
@@ -38,7 +38,7 @@ impl Reorg {
         for depth in 1..max_recoverable_reorg_depth {
           let index_block_hash = index.block_hash(height.checked_sub(depth))?;
           let bitcoind_block_hash = index
-            .client
+            .client()
             .get_block_hash(height.saturating_sub(depth))
             .into_option()?;
 
@@ -62,8 +62,11 @@ impl Reorg {
 
     let mut wtx = index.begin_write()?;
 
-    let oldest_savepoint =
-      wtx.get_persistent_savepoint(wtx.list_persistent_savepoints()?.min().unwrap())?;
+    let Some(oldest_savepoint) = wtx.list_persistent_savepoints()?.min() else {
+      return Err(anyhow!(ReorgError::Unrecoverable));
+    };
+
+    let oldest_savepoint = wtx.get_persistent_savepoint(oldest_savepoint)?;
 
     wtx.restore_savepoint(&oldest_savepoint)?;
 
@@ -85,7 +88,7 @@ impl Reorg {
 
     if (height < SAVEPOINT_INTERVAL || height % SAVEPOINT_INTERVAL == 0)
       && index
-        .client
+        .client()
         .get_blockchain_info()?
         .headers
         .saturating_sub(height)
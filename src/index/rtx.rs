@@ -48,4 +48,14 @@ impl Rtx<'_> {
       ),
     }
   }
+
+  pub(crate) fn block_header(&self, height: u64) -> Result<Option<Header>> {
+    Ok(
+      self
+        .0
+        .open_table(HEIGHT_TO_BLOCK_HEADER)?
+        .get(height)?
+        .map(|header| Header::load(*header.value())),
+    )
+  }
 }
@@ -1,6 +1,10 @@
 use {
   self::{inscription_updater::InscriptionUpdater, rune_updater::RuneUpdater},
-  super::{fetcher::Fetcher, *},
+  super::{
+    block_source::{self, ActiveChainFileBlockSource, BlockSource, RpcBlockSource},
+    fetcher::Fetcher,
+    *,
+  },
   futures::future::try_join_all,
   std::sync::mpsc,
   tokio::sync::mpsc::{error::TryRecvError, Receiver, Sender},
@@ -9,6 +13,18 @@ use {
 mod inscription_updater;
 mod rune_updater;
 
+/// Logs a message with the local variables in `fields` attached, through
+/// `tracing` if the `tracing` feature is enabled, and through `log` otherwise.
+macro_rules! log_with_fields {
+  ($level:ident, { $($field:ident),+ $(,)? }, $($message:tt)+) => {
+    #[cfg(feature = "tracing")]
+    tracing::$level!($($field,)+ $($message)+);
+
+    #[cfg(not(feature = "tracing"))]
+    log::$level!($($field = $field),+; $($message)+);
+  };
+}
+
 pub(crate) struct BlockData {
   pub(crate) header: Header,
   pub(crate) txdata: Vec<(Transaction, Txid)>,
@@ -30,32 +46,120 @@ impl From<Block> for BlockData {
   }
 }
 
+const BLOCK_TIMES_WINDOW: usize = 100;
+
+fn format_duration(duration: Duration) -> String {
+  let minutes = duration.as_secs() / 60;
+
+  if minutes >= 60 {
+    format!("{}h{}m", minutes / 60, minutes % 60)
+  } else {
+    format!("{minutes}m")
+  }
+}
+
+/// Assigns sats from `input_sat_ranges` to outputs of the given values in
+/// order, returning each output's ranges and the number of input ranges that
+/// were split. Unassigned sats are left in `input_sat_ranges`.
+pub(crate) fn assign_sat_ranges(
+  output_values: impl IntoIterator<Item = u64>,
+  input_sat_ranges: &mut LotQueue,
+) -> Result<(Vec<Vec<Lot>>, u64)> {
+  let mut outputs = Vec::new();
+  let mut splits = 0;
+
+  for value in output_values {
+    let mut sats: Vec<Lot> = Vec::new();
+
+    let mut remaining = value;
+    while remaining > 0 {
+      let (assigned, remainder) = input_sat_ranges.consume(remaining)?;
+
+      if remainder.is_some() {
+        splits += 1;
+      }
+
+      // Merge with the previous range unless that would hide a rare sat
+      match sats.last_mut() {
+        Some(last) if Sat(assigned.start).is_common() => match last.merge(assigned) {
+          Some(merged) => *last = merged,
+          None => sats.push(assigned),
+        },
+        _ => sats.push(assigned),
+      }
+
+      remaining -= assigned.len();
+    }
+
+    outputs.push(sats);
+  }
+
+  Ok((outputs, splits))
+}
+
+#[derive(Default)]
+struct PhaseTimes {
+  range_reads: Duration,
+  range_assignment: Duration,
+  satpoint_inserts: Duration,
+}
+
 pub(crate) struct Updater<'index> {
+  block_events: Vec<BlockEvent>,
   range_cache: HashMap<OutPointValue, Vec<u8>>,
+  range_cache_bytes: usize,
+  block_times: VecDeque<Instant>,
+  chain_block_count: u64,
   height: u64,
   index: &'index Index,
+  next_event_sequence_number: u64,
   sat_ranges_since_flush: u64,
   outputs_cached: u64,
   outputs_inserted_since_flush: u64,
   outputs_traversed: u64,
+  outputs_uncached: u64,
 }
 
 impl<'index> Updater<'_> {
   pub(crate) fn new(index: &'index Index) -> Result<Updater<'index>> {
     Ok(Updater {
+      block_events: Vec::new(),
       range_cache: HashMap::new(),
+      range_cache_bytes: 0,
+      block_times: VecDeque::new(),
+      chain_block_count: 0,
       height: index.block_count()?,
       index,
+      next_event_sequence_number: index
+        .begin_read()?
+        .0
+        .open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ID)?
+        .iter()?
+        .next_back()
+        .and_then(|result| result.ok())
+        .map(|(number, _id)| number.value() + 1)
+        .unwrap_or(0),
       sat_ranges_since_flush: 0,
       outputs_cached: 0,
       outputs_inserted_since_flush: 0,
       outputs_traversed: 0,
+      outputs_uncached: 0,
     })
   }
 
   pub(crate) fn update_index(&mut self) -> Result {
+    if let Some(height_limit) = self.index.height_limit {
+      if height_limit < self.height {
+        bail!(
+          "height limit {height_limit} is below index block count, index already contains {} blocks",
+          self.height
+        );
+      }
+    }
+
     let mut wtx = self.index.begin_write()?;
-    let starting_height = self.index.client.get_block_count()? + 1;
+    let starting_height = self.index.client().get_block_count()? + 1;
+    self.chain_block_count = starting_height;
 
     wtx
       .open_table(WRITE_TRANSACTION_STARTING_BLOCK_COUNT_TO_TIMESTAMP)?
@@ -82,13 +186,32 @@ impl<'index> Updater<'_> {
       Some(progress_bar)
     };
 
-    let rx = Self::fetch_blocks_from(self.index, self.height, self.index.index_sats)?;
+    let rx = Self::fetch_blocks_from(
+      self.index,
+      self.height,
+      self.index.index_sats
+        || self.index.index_spent_outputs
+        || self.index.index_txids
+        || self.index.index_addresses,
+    )?;
 
     let (mut outpoint_sender, mut value_receiver) = Self::spawn_fetcher(self.index)?;
 
     let mut uncommitted = 0;
     let mut value_cache = HashMap::new();
-    while let Ok(block) = rx.recv() {
+    loop {
+      let fetch_start = Instant::now();
+
+      let Ok(block) = rx.recv() else {
+        break;
+      };
+
+      log::debug!(
+        "Waited {} ms to fetch block {}",
+        fetch_start.elapsed().as_millis(),
+        self.height
+      );
+
       self.index_block(
         self.index,
         &mut outpoint_sender,
@@ -102,7 +225,7 @@ impl<'index> Updater<'_> {
         progress_bar.inc(1);
 
         if progress_bar.position() > progress_bar.length().unwrap() {
-          if let Ok(count) = self.index.client.get_block_count() {
+          if let Ok(count) = self.index.client().get_block_count() {
             progress_bar.set_length(count + 1);
           } else {
             log::warn!("Failed to fetch latest block height");
@@ -112,7 +235,13 @@ impl<'index> Updater<'_> {
 
       uncommitted += 1;
 
-      if uncommitted == 5000 {
+      let range_cache_full = self
+        .index
+        .range_cache_size
+        .map(|range_cache_size| self.range_cache_bytes > range_cache_size)
+        .unwrap_or_default();
+
+      if uncommitted == self.index.commit_interval || range_cache_full {
         self.commit(wtx, value_cache)?;
         value_cache = HashMap::new();
         uncommitted = 0;
@@ -158,87 +287,32 @@ impl<'index> Updater<'_> {
 
   fn fetch_blocks_from(
     index: &Index,
-    mut height: u64,
-    index_sats: bool,
-  ) -> Result<mpsc::Receiver<BlockData>> {
-    let (tx, rx) = mpsc::sync_channel(32);
-
-    let height_limit = index.height_limit;
-
-    let client = index.options.bitcoin_rpc_client()?;
-
-    let first_inscription_height = index.first_inscription_height;
-
-    thread::spawn(move || loop {
-      if let Some(height_limit) = height_limit {
-        if height >= height_limit {
-          break;
-        }
-      }
-
-      match Self::get_block_with_retries(&client, height, index_sats, first_inscription_height) {
-        Ok(Some(block)) => {
-          if let Err(err) = tx.send(block.into()) {
-            log::info!("Block receiver disconnected: {err}");
-            break;
-          }
-          height += 1;
-        }
-        Ok(None) => break,
-        Err(err) => {
-          log::error!("failed to fetch block {height}: {err}");
-          break;
-        }
-      }
-    });
-
-    Ok(rx)
-  }
-
-  fn get_block_with_retries(
-    client: &Client,
     height: u64,
-    index_sats: bool,
-    first_inscription_height: u64,
-  ) -> Result<Option<Block>> {
-    let mut errors = 0;
-    loop {
-      match client
-        .get_block_hash(height)
-        .into_option()
-        .and_then(|option| {
-          option
-            .map(|hash| {
-              if index_sats || height >= first_inscription_height {
-                Ok(client.get_block(&hash)?)
-              } else {
-                Ok(Block {
-                  header: client.get_block_header(&hash)?,
-                  txdata: Vec::new(),
-                })
-              }
-            })
-            .transpose()
-        }) {
-        Err(err) => {
-          if cfg!(test) {
-            return Err(err);
-          }
-
-          errors += 1;
-          let seconds = 1 << errors;
-          log::warn!("failed to fetch block {height}, retrying in {seconds}s: {err}");
-
-          if seconds > 120 {
-            log::error!("would sleep for more than 120s, giving up");
-            return Err(err);
-          }
+    full_blocks: bool,
+  ) -> Result<mpsc::Receiver<BlockData>> {
+    let mut block_sources: Vec<Box<dyn BlockSource>> = Vec::new();
 
-          thread::sleep(Duration::from_secs(seconds));
-        }
-        Ok(result) => return Ok(result),
-      }
+    if let Some(block_files) = &index.block_files {
+      block_sources.push(Box::new(ActiveChainFileBlockSource {
+        block_files: block_files.clone(),
+        client: index.block_client.clone(),
+      }));
     }
+
+    block_sources.push(Box::new(RpcBlockSource {
+      block_cache: index.block_cache.clone(),
+      client: index.block_client.clone(),
+      first_inscription_height: index.first_inscription_height,
+      full_blocks,
+      max_retries: index.options.rpc_max_retries,
+    }));
+
+    Ok(block_source::prefetch(
+      block_sources,
+      height,
+      index.height_limit,
+      index.options.prefetch_blocks,
+    ))
   }
 
   fn spawn_fetcher(index: &Index) -> Result<(Sender<OutPoint>, Receiver<u64>)> {
@@ -321,15 +395,33 @@ impl<'index> Updater<'_> {
   ) -> Result<()> {
     Reorg::detect_reorg(&block, self.height, self.index)?;
 
+    #[cfg(feature = "tracing")]
+    let _span = tracing::info_span!("index_block", height = self.height).entered();
+
+    let height = self.height;
     let start = Instant::now();
     let mut sat_ranges_written = 0;
     let mut outputs_in_block = 0;
+    let mut phase_times = PhaseTimes::default();
 
-    log::info!(
-      "Block {} at {} with {} transactions…",
-      self.height,
+    self.block_times.push_back(start);
+    if self.block_times.len() > BLOCK_TIMES_WINDOW {
+      self.block_times.pop_front();
+    }
+
+    log_with_fields!(
+      info,
+      { height },
+      "Block {height} at {} with {} transactions{}…",
       timestamp(block.header.time),
-      block.txdata.len()
+      block.txdata.len(),
+      self
+        .blocks_per_second()
+        .map(|blocks_per_second| format!(
+          ", ~{} remaining",
+          format_duration(self.estimate_remaining_time(blocks_per_second))
+        ))
+        .unwrap_or_default(),
     );
 
     // If value_receiver still has values something went wrong with the last block
@@ -391,7 +483,11 @@ impl<'index> Updater<'_> {
       }
     }
 
+    let mut content_hash_to_inscription_ids =
+      wtx.open_multimap_table(CONTENT_HASH_TO_INSCRIPTION_IDS)?;
     let mut height_to_block_hash = wtx.open_table(HEIGHT_TO_BLOCK_HASH)?;
+    let mut height_to_hash_checksum = wtx.open_table(HEIGHT_TO_HASH_CHECKSUM)?;
+    let mut height_to_block_header = wtx.open_table(HEIGHT_TO_BLOCK_HEADER)?;
     let mut height_to_last_sequence_number = wtx.open_table(HEIGHT_TO_LAST_SEQUENCE_NUMBER)?;
     let mut inscription_id_to_inscription_entry =
       wtx.open_table(INSCRIPTION_ID_TO_INSCRIPTION_ENTRY)?;
@@ -427,6 +523,7 @@ impl<'index> Updater<'_> {
 
     let mut inscription_updater = InscriptionUpdater::new(
       self.height,
+      &mut content_hash_to_inscription_ids,
       &mut inscription_id_to_children,
       &mut inscription_id_to_satpoint,
       value_receiver,
@@ -446,39 +543,60 @@ impl<'index> Updater<'_> {
 
     if self.index.index_sats {
       let mut sat_to_satpoint = wtx.open_table(SAT_TO_SATPOINT)?;
+      let mut sat_to_satpoint_history = wtx.open_multimap_table(SAT_TO_SATPOINT_HISTORY)?;
       let mut outpoint_to_sat_ranges = wtx.open_table(OUTPOINT_TO_SAT_RANGES)?;
 
-      let mut coinbase_inputs = VecDeque::new();
+      let mut coinbase_inputs = LotQueue::default();
 
       let h = Height(self.height);
       if h.subsidy() > 0 {
         let start = h.starting_sat();
-        coinbase_inputs.push_front((start.n(), (start + h.subsidy()).n()));
+        // the first sat of each block is the only non-common sat it mints
+        wtx
+          .open_table(SAT_TO_RARITY)?
+          .insert(&start.n(), &u8::from(start.rarity()))?;
+        coinbase_inputs.extend_from_excess(Lot {
+          start: start.n(),
+          end: (start + h.subsidy()).n(),
+        });
         self.sat_ranges_since_flush += 1;
       }
 
       for (tx_offset, (tx, txid)) in block.txdata.iter().enumerate().skip(1) {
         log::trace!("Indexing transaction {tx_offset}…");
 
-        let mut input_sat_ranges = VecDeque::new();
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!("index_transaction", %txid).entered();
+
+        let mut input_sat_ranges = LotQueue::default();
 
         for input in &tx.input {
           let key = input.previous_output.store();
 
+          let read_start = Instant::now();
+
           let sat_ranges = match self.range_cache.remove(&key) {
             Some(sat_ranges) => {
               self.outputs_cached += 1;
+              self.range_cache_bytes -= key.len() + sat_ranges.len();
               sat_ranges
             }
-            None => outpoint_to_sat_ranges
-              .remove(&key)?
-              .ok_or_else(|| anyhow!("Could not find outpoint {} in index", input.previous_output))?
-              .value()
-              .to_vec(),
+            None => {
+              self.outputs_uncached += 1;
+              outpoint_to_sat_ranges
+                .remove(&key)?
+                .ok_or_else(|| {
+                  anyhow!("Could not find outpoint {} in index", input.previous_output)
+                })?
+                .value()
+                .to_vec()
+            }
           };
 
+          phase_times.range_reads += read_start.elapsed();
+
           for chunk in sat_ranges.chunks_exact(11) {
-            input_sat_ranges.push_back(SatRange::load(chunk.try_into().unwrap()));
+            input_sat_ranges.push_back(Lot::load(chunk.try_into().unwrap()));
           }
         }
 
@@ -486,11 +604,13 @@ impl<'index> Updater<'_> {
           tx,
           *txid,
           &mut sat_to_satpoint,
+          &mut sat_to_satpoint_history,
           &mut input_sat_ranges,
           &mut sat_ranges_written,
           &mut outputs_in_block,
           &mut inscription_updater,
           index_inscriptions,
+          &mut phase_times,
         )?;
 
         coinbase_inputs.extend(input_sat_ranges);
@@ -501,11 +621,13 @@ impl<'index> Updater<'_> {
           tx,
           *txid,
           &mut sat_to_satpoint,
+          &mut sat_to_satpoint_history,
           &mut coinbase_inputs,
           &mut sat_ranges_written,
           &mut outputs_in_block,
           &mut inscription_updater,
           index_inscriptions,
+          &mut phase_times,
         )?;
       }
 
@@ -515,21 +637,19 @@ impl<'index> Updater<'_> {
           .map(|ranges| ranges.value().to_vec())
           .unwrap_or_default();
 
-        for (start, end) in coinbase_inputs {
-          if !Sat(start).is_common() {
-            sat_to_satpoint.insert(
-              &start,
-              &SatPoint {
-                outpoint: OutPoint::null(),
-                offset: lost_sats,
-              }
-              .store(),
-            )?;
+        for lot in coinbase_inputs {
+          if !Sat(lot.start).is_common() {
+            let satpoint = SatPoint {
+              outpoint: OutPoint::null(),
+              offset: lost_sats,
+            };
+            sat_to_satpoint.insert(&lot.start, &satpoint.store())?;
+            sat_to_satpoint_history.insert(&lot.start, &(self.height, satpoint).store())?;
           }
 
-          lost_sat_ranges.extend_from_slice(&(start, end).store());
+          lost_sat_ranges.extend_from_slice(&lot.store());
 
-          lost_sats += end - start;
+          lost_sats += lot.len();
         }
 
         outpoint_to_sat_ranges.insert(&OutPoint::null().store(), lost_sat_ranges.as_slice())?;
@@ -563,14 +683,101 @@ impl<'index> Updater<'_> {
       &inscription_updater.unbound_inscriptions,
     )?;
 
-    height_to_block_hash.insert(&self.height, &block.header.block_hash().store())?;
+    let inscriptions_in_block = inscription_updater.cursed_inscription_count
+      + inscription_updater.blessed_inscription_count
+      - cursed_inscription_count
+      - blessed_inscription_count;
+
+    let block_hash = block.header.block_hash().store();
+    height_to_block_hash.insert(&self.height, &block_hash)?;
+    height_to_hash_checksum.insert(&self.height, &Index::hash_checksum(&block_hash))?;
+
+    if self.index.block_events.receiver_count() > 0 {
+      self.block_events.push(BlockEvent {
+        block_hash: block.header.block_hash(),
+        height: self.height,
+        inscriptions_added: inscriptions_in_block,
+        ordinals_mined: Height(self.height).subsidy(),
+      });
+    }
+    height_to_block_header.insert(&self.height, &block.header.store())?;
+
+    if self.index.index_spent_outputs {
+      let mut spent_outpoint_to_height = wtx.open_table(SPENT_OUTPOINT_TO_HEIGHT)?;
+
+      for (tx, _txid) in block.txdata.iter().skip(1) {
+        for input in &tx.input {
+          spent_outpoint_to_height.insert(&input.previous_output.store(), &self.height)?;
+        }
+      }
+    }
+
+    if self.index.index_txids {
+      let mut txid_to_block_height = wtx.open_table(TXID_TO_BLOCK_HEIGHT)?;
+
+      for (_tx, txid) in &block.txdata {
+        txid_to_block_height.insert(&txid.store(), &self.height)?;
+      }
+    }
+
+    if self.index.index_addresses {
+      let mut outpoint_to_script_pubkey = wtx.open_table(OUTPOINT_TO_SCRIPT_PUBKEY)?;
+      let mut script_pubkey_to_outpoint = wtx.open_multimap_table(SCRIPT_PUBKEY_TO_OUTPOINT)?;
+
+      for (tx, txid) in &block.txdata {
+        for input in &tx.input {
+          let outpoint = input.previous_output.store();
+          if let Some(script_pubkey) = outpoint_to_script_pubkey.remove(&outpoint)? {
+            script_pubkey_to_outpoint.remove(script_pubkey.value(), &outpoint)?;
+          }
+        }
+
+        for (vout, output) in tx.output.iter().enumerate() {
+          if output.script_pubkey.is_op_return() {
+            continue;
+          }
+
+          let outpoint = OutPoint {
+            txid: *txid,
+            vout: vout.try_into().unwrap(),
+          }
+          .store();
+
+          outpoint_to_script_pubkey.insert(&outpoint, output.script_pubkey.as_bytes())?;
+          script_pubkey_to_outpoint.insert(output.script_pubkey.as_bytes(), &outpoint)?;
+        }
+      }
+    }
 
     self.height += 1;
     self.outputs_traversed += outputs_in_block;
 
-    log::info!(
-      "Wrote {sat_ranges_written} sat ranges from {outputs_in_block} outputs in {} ms",
-      (Instant::now() - start).as_millis(),
+    let elapsed_ms = u64::try_from((Instant::now() - start).as_millis()).unwrap();
+    let range_reads_ms = u64::try_from(phase_times.range_reads.as_millis()).unwrap();
+    let range_assignment_ms = u64::try_from(phase_times.range_assignment.as_millis()).unwrap();
+    let satpoint_inserts_ms = u64::try_from(phase_times.satpoint_inserts.as_millis()).unwrap();
+
+    log_with_fields!(
+      info,
+      {
+        height,
+        sat_ranges_written,
+        outputs_in_block,
+        inscriptions_in_block,
+        elapsed_ms,
+      },
+      "Wrote {sat_ranges_written} sat ranges from {outputs_in_block} outputs and {inscriptions_in_block} inscriptions in {elapsed_ms} ms",
+    );
+
+    log_with_fields!(
+      debug,
+      {
+        height,
+        range_reads_ms,
+        range_assignment_ms,
+        satpoint_inserts_ms,
+      },
+      "Spent {range_reads_ms} ms reading sat ranges, {range_assignment_ms} ms assigning sat ranges, and {satpoint_inserts_ms} ms inserting satpoints",
     );
 
     Ok(())
@@ -581,67 +788,84 @@ impl<'index> Updater<'_> {
     tx: &Transaction,
     txid: Txid,
     sat_to_satpoint: &mut Table<u64, &SatPointValue>,
-    input_sat_ranges: &mut VecDeque<(u64, u64)>,
+    sat_to_satpoint_history: &mut MultimapTable<u64, &SatPointHistoryValue>,
+    input_sat_ranges: &mut LotQueue,
     sat_ranges_written: &mut u64,
     outputs_traversed: &mut u64,
     inscription_updater: &mut InscriptionUpdater,
     index_inscriptions: bool,
+    phase_times: &mut PhaseTimes,
   ) -> Result {
     if index_inscriptions {
       inscription_updater.index_envelopes(tx, txid, Some(input_sat_ranges))?;
     }
 
-    for (vout, output) in tx.output.iter().enumerate() {
-      let outpoint = OutPoint {
-        vout: vout.try_into().unwrap(),
-        txid,
-      };
-      let mut sats = Vec::new();
-
-      let mut remaining = output.value;
-      while remaining > 0 {
-        let range = input_sat_ranges
-          .pop_front()
-          .ok_or_else(|| anyhow!("insufficient inputs for transaction outputs"))?;
-
-        if !Sat(range.0).is_common() {
-          sat_to_satpoint.insert(
-            &range.0,
-            &SatPoint {
-              outpoint,
-              offset: output.value - remaining,
-            }
-            .store(),
-          )?;
-        }
-
-        let count = range.1 - range.0;
-
-        let assigned = if count > remaining {
-          self.sat_ranges_since_flush += 1;
-          let middle = range.0 + remaining;
-          input_sat_ranges.push_front((middle, range.1));
-          (range.0, middle)
-        } else {
-          range
-        };
-
-        sats.extend_from_slice(&assigned.store());
-
-        remaining -= assigned.1 - assigned.0;
+    let assignment_start = Instant::now();
+    let outputs = self.assign_sat_ranges(tx, txid, input_sat_ranges)?;
+    phase_times.range_assignment += assignment_start.elapsed();
 
-        *sat_ranges_written += 1;
+    for (outpoint, sats, rare_sats) in outputs {
+      let insert_start = Instant::now();
+      for (sat, satpoint) in rare_sats {
+        sat_to_satpoint.insert(&sat, &satpoint.store())?;
+        sat_to_satpoint_history.insert(&sat, &(self.height, satpoint).store())?;
       }
+      phase_times.satpoint_inserts += insert_start.elapsed();
 
+      *sat_ranges_written += u64::try_from(sats.len() / 11).unwrap();
       *outputs_traversed += 1;
 
-      self.range_cache.insert(outpoint.store(), sats);
+      let key = outpoint.store();
+      self.range_cache_bytes += key.len() + sats.len();
+      self.range_cache.insert(key, sats);
       self.outputs_inserted_since_flush += 1;
     }
 
     Ok(())
   }
 
+  fn assign_sat_ranges(
+    &mut self,
+    tx: &Transaction,
+    txid: Txid,
+    input_sat_ranges: &mut LotQueue,
+  ) -> Result<Vec<(OutPoint, Vec<u8>, Vec<(u64, SatPoint)>)>> {
+    let (outputs, splits) = assign_sat_ranges(
+      tx.output.iter().map(|output| output.value),
+      input_sat_ranges,
+    )?;
+
+    self.sat_ranges_since_flush += splits;
+
+    Ok(
+      outputs
+        .into_iter()
+        .enumerate()
+        .map(|(vout, sats)| {
+          let outpoint = OutPoint {
+            vout: vout.try_into().unwrap(),
+            txid,
+          };
+
+          let mut rare_sats = Vec::new();
+          let mut offset = 0;
+          for lot in &sats {
+            if !Sat(lot.start).is_common() {
+              rare_sats.push((lot.start, SatPoint { outpoint, offset }));
+            }
+            offset += lot.len();
+          }
+
+          (
+            outpoint,
+            sats.iter().flat_map(|range| range.store()).collect(),
+            rare_sats,
+          )
+        })
+        .collect(),
+    )
+  }
+
   fn index_block_inscription_numbers(
     &mut self,
     height_to_sequence_number: &mut Table<u64, u64>,
@@ -657,13 +881,48 @@ impl<'index> Updater<'_> {
     Ok(())
   }
 
+  fn blocks_per_second(&self) -> Option<f64> {
+    let elapsed = self
+      .block_times
+      .back()?
+      .duration_since(*self.block_times.front()?)
+      .as_secs_f64();
+
+    if elapsed == 0.0 {
+      return None;
+    }
+
+    Some((self.block_times.len() - 1) as f64 / elapsed)
+  }
+
+  fn estimate_remaining_time(&self, blocks_per_second: f64) -> Duration {
+    let remaining_blocks = self.chain_block_count.saturating_sub(self.height);
+
+    Duration::try_from_secs_f64(remaining_blocks as f64 / blocks_per_second)
+      .unwrap_or(Duration::MAX)
+  }
+
+  fn outputs_cached_ratio(&self) -> f64 {
+    let lookups = self.outputs_cached + self.outputs_uncached;
+
+    if lookups == 0 {
+      0.0
+    } else {
+      self.outputs_cached as f64 / lookups as f64
+    }
+  }
+
   fn commit(&mut self, wtx: WriteTransaction, value_cache: HashMap<OutPoint, u64>) -> Result {
+    self.check_index_size()?;
+
     log::info!(
-      "Committing at block height {}, {} outputs traversed, {} in map, {} cached",
+      "Committing at block height {}, {} outputs traversed, {} in map ({:.1} MiB), {} cached ({:.1}% hit rate)",
       self.height,
       self.outputs_traversed,
       self.range_cache.len(),
-      self.outputs_cached
+      self.range_cache_bytes as f64 / f64::from(1 << 20),
+      self.outputs_cached,
+      self.outputs_cached_ratio() * 100.,
     );
 
     if self.index.index_sats {
@@ -680,6 +939,8 @@ impl<'index> Updater<'_> {
         outpoint_to_sat_ranges.insert(&outpoint, sat_range.as_slice())?;
       }
 
+      self.range_cache_bytes = 0;
+
       self.outputs_inserted_since_flush = 0;
     }
 
@@ -691,15 +952,108 @@ impl<'index> Updater<'_> {
       }
     }
 
+    Index::increment_statistic(&wtx, Statistic::OutputsCached, self.outputs_cached)?;
+    self.outputs_cached = 0;
     Index::increment_statistic(&wtx, Statistic::OutputsTraversed, self.outputs_traversed)?;
     self.outputs_traversed = 0;
+    Index::increment_statistic(&wtx, Statistic::OutputsUncached, self.outputs_uncached)?;
+    self.outputs_uncached = 0;
     Index::increment_statistic(&wtx, Statistic::SatRanges, self.sat_ranges_since_flush)?;
     self.sat_ranges_since_flush = 0;
     Index::increment_statistic(&wtx, Statistic::Commits, 1)?;
+
+    let new_inscriptions = self.new_inscriptions(&wtx)?;
+
     wtx.commit()?;
 
+    for event in self.block_events.drain(..) {
+      self.index.block_events.send(event).ok();
+    }
+
+    for (inscription_id, entry) in new_inscriptions {
+      let content_type = self
+        .index
+        .get_inscription_by_id(inscription_id)
+        .ok()
+        .flatten()
+        .and_then(|inscription| inscription.content_type().map(str::to_string));
+
+      self
+        .index
+        .inscription_events
+        .send(InscriptionEvent {
+          block_height: entry.height,
+          content_type,
+          inscription_id,
+          sat: entry.sat,
+          txid: inscription_id.txid,
+        })
+        .ok();
+    }
+
     Reorg::update_savepoints(self.index, self.height)?;
 
     Ok(())
   }
+
+  fn check_index_size(&self) -> Result {
+    let size = fs::metadata(&self.index.path)?.len();
+
+    if let Some(max_index_size) = self.index.options.max_index_size {
+      if size > max_index_size {
+        bail!(
+          "index file `{}` is {size} bytes, which exceeds the maximum index size of {max_index_size} bytes",
+          self.index.path.display()
+        );
+      }
+    }
+
+    if let Some(warn_index_size) = self.index.options.warn_index_size {
+      if size > warn_index_size {
+        log::warn!(
+          "index file `{}` is {size} bytes, which exceeds the warning size of {warn_index_size} bytes",
+          self.index.path.display()
+        );
+      }
+    }
+
+    Ok(())
+  }
+
+  fn new_inscriptions(
+    &mut self,
+    wtx: &WriteTransaction,
+  ) -> Result<Vec<(InscriptionId, InscriptionEntry)>> {
+    let sequence_number_to_inscription_id = wtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ID)?;
+
+    let next_sequence_number = sequence_number_to_inscription_id
+      .iter()?
+      .next_back()
+      .and_then(|result| result.ok())
+      .map(|(number, _id)| number.value() + 1)
+      .unwrap_or(0);
+
+    let start = self.next_event_sequence_number;
+    self.next_event_sequence_number = next_sequence_number;
+
+    if self.index.inscription_events.receiver_count() == 0 {
+      return Ok(Vec::new());
+    }
+
+    let inscription_id_to_inscription_entry =
+      wtx.open_table(INSCRIPTION_ID_TO_INSCRIPTION_ENTRY)?;
+
+    let mut new_inscriptions = Vec::new();
+
+    for result in sequence_number_to_inscription_id.range(start..)? {
+      let (_number, id) = result?;
+      let inscription_id = InscriptionId::load(*id.value());
+
+      if let Some(entry) = inscription_id_to_inscription_entry.get(&inscription_id.store())? {
+        new_inscriptions.push((inscription_id, InscriptionEntry::load(entry.value())));
+      }
+    }
+
+    Ok(new_inscriptions)
+  }
 }
@@ -1,4 +1,4 @@
-use {super::*, inscription::Curse};
+use {super::*, bitcoin::hashes::sha256, inscription::Curse};
 
 #[derive(Debug, Clone)]
 pub(super) struct Flotsam {
@@ -22,6 +22,8 @@ enum Origin {
 }
 
 pub(super) struct InscriptionUpdater<'a, 'db, 'tx> {
+  content_hash_to_id:
+    &'a mut MultimapTable<'db, 'tx, &'static [u8; 32], &'static InscriptionIdValue>,
   flotsam: Vec<Flotsam>,
   height: u64,
   id_to_children:
@@ -48,6 +50,12 @@ pub(super) struct InscriptionUpdater<'a, 'db, 'tx> {
 impl<'a, 'db, 'tx> InscriptionUpdater<'a, 'db, 'tx> {
   pub(super) fn new(
     height: u64,
+    content_hash_to_id: &'a mut MultimapTable<
+      'db,
+      'tx,
+      &'static [u8; 32],
+      &'static InscriptionIdValue,
+    >,
     id_to_children: &'a mut MultimapTable<
       'db,
       'tx,
@@ -82,6 +90,7 @@ impl<'a, 'db, 'tx> InscriptionUpdater<'a, 'db, 'tx> {
       .unwrap_or(0);
 
     Ok(Self {
+      content_hash_to_id,
       flotsam: Vec::new(),
       height,
       id_to_children,
@@ -108,7 +117,7 @@ impl<'a, 'db, 'tx> InscriptionUpdater<'a, 'db, 'tx> {
     &mut self,
     tx: &Transaction,
     txid: Txid,
-    input_sat_ranges: Option<&VecDeque<(u64, u64)>>,
+    input_sat_ranges: Option<&LotQueue>,
   ) -> Result {
     let mut envelopes = ParsedEnvelope::from_transaction(tx).into_iter().peekable();
     let mut floating_inscriptions = Vec::new();
@@ -242,6 +251,13 @@ impl<'a, 'db, 'tx> InscriptionUpdater<'a, 'db, 'tx> {
           );
         }
 
+        if let Some(body) = inscription.payload.body() {
+          self.content_hash_to_id.insert(
+            &sha256::Hash::hash(body).to_byte_array(),
+            &inscription_id.store(),
+          )?;
+        }
+
         floating_inscriptions.push(Flotsam {
           inscription_id,
           offset,
@@ -406,17 +422,14 @@ impl<'a, 'db, 'tx> InscriptionUpdater<'a, 'db, 'tx> {
     }
   }
 
-  fn calculate_sat(
-    input_sat_ranges: Option<&VecDeque<(u64, u64)>>,
-    input_offset: u64,
-  ) -> Option<Sat> {
+  fn calculate_sat(input_sat_ranges: Option<&LotQueue>, input_offset: u64) -> Option<Sat> {
     let mut sat = None;
     if let Some(input_sat_ranges) = input_sat_ranges {
       let mut offset = 0;
-      for (start, end) in input_sat_ranges {
-        let size = end - start;
+      for lot in input_sat_ranges {
+        let size = lot.len();
         if offset + size > input_offset {
-          let n = start + input_offset - offset;
+          let n = lot.start + input_offset - offset;
           sat = Some(Sat(n));
           break;
         }
@@ -428,7 +441,7 @@ impl<'a, 'db, 'tx> InscriptionUpdater<'a, 'db, 'tx> {
 
   fn update_inscription_location(
     &mut self,
-    input_sat_ranges: Option<&VecDeque<(u64, u64)>>,
+    input_sat_ranges: Option<&LotQueue>,
     flotsam: Flotsam,
     new_satpoint: SatPoint,
   ) -> Result {
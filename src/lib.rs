@@ -23,6 +23,7 @@ use {
     height::Height,
     index::{Index, List, RuneEntry},
     inscription_id::InscriptionId,
+    log_format::LogFormat,
     media::Media,
     options::Options,
     outgoing::Outgoing,
@@ -80,6 +81,7 @@ use {
 
 pub use crate::{
   fee_rate::FeeRate,
+  index::decode_sat_ranges,
   inscription::Inscription,
   object::Object,
   rarity::Rarity,
@@ -119,6 +121,7 @@ mod height;
 mod index;
 mod inscription;
 pub mod inscription_id;
+mod log_format;
 mod media;
 mod object;
 mod options;
@@ -174,7 +177,9 @@ fn gracefully_shutdown_indexer() {
 }
 
 pub fn main() {
-  env_logger::init();
+  let arguments = Arguments::parse();
+
+  arguments.options.log_format.init_logger();
 
   ctrlc::set_handler(move || {
     if SHUTTING_DOWN.fetch_or(true, atomic::Ordering::Relaxed) {
@@ -191,7 +196,7 @@ pub fn main() {
   })
   .expect("Error setting <CTRL-C> handler");
 
-  match Arguments::parse().run() {
+  match arguments.run() {
     Err(err) => {
       eprintln!("error: {err}");
       err
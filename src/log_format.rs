@@ -0,0 +1,71 @@
+use {super::*, clap::ValueEnum, std::io::Write};
+
+#[derive(Default, ValueEnum, Copy, Clone, Debug, PartialEq)]
+pub(crate) enum LogFormat {
+  #[default]
+  Human,
+  Json,
+}
+
+impl LogFormat {
+  pub(crate) fn init_logger(self) {
+    let mut builder = env_logger::Builder::from_default_env();
+
+    if self == Self::Json {
+      builder.format(|buf, record| writeln!(buf, "{}", Self::json(Utc::now(), record)));
+    }
+
+    builder.init();
+  }
+
+  fn json(timestamp: DateTime<Utc>, record: &log::Record) -> serde_json::Value {
+    serde_json::json!({
+      "timestamp": timestamp.to_rfc3339(),
+      "level": record.level().as_str(),
+      "module": record.module_path(),
+      "height": record
+        .key_values()
+        .get(log::kv::Key::from_str("height"))
+        .and_then(|height| height.to_u64()),
+      "message": record.args().to_string(),
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn json() {
+    assert_eq!(
+      LogFormat::json(
+        Utc.timestamp_opt(0, 0).unwrap(),
+        &log::Record::builder()
+          .args(format_args!("indexing block {}", 100))
+          .level(log::Level::Info)
+          .module_path(Some("ord::index::updater"))
+          .key_values(&[("height", 100u64)].as_slice())
+          .build(),
+      )
+      .to_string(),
+      r#"{"timestamp":"1970-01-01T00:00:00+00:00","level":"INFO","module":"ord::index::updater","height":100,"message":"indexing block 100"}"#,
+    );
+  }
+
+  #[test]
+  fn json_without_height() {
+    assert_eq!(
+      LogFormat::json(
+        Utc.timestamp_opt(0, 0).unwrap(),
+        &log::Record::builder()
+          .args(format_args!("listening"))
+          .level(log::Level::Info)
+          .module_path(Some("ord::subcommand::server"))
+          .build(),
+      )
+      .to_string(),
+      r#"{"timestamp":"1970-01-01T00:00:00+00:00","level":"INFO","module":"ord::subcommand::server","height":null,"message":"listening"}"#,
+    );
+  }
+}
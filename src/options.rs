@@ -1,4 +1,10 @@
-use {super::*, bitcoincore_rpc::Auth};
+use {
+  super::*,
+  bitcoincore_rpc::{
+    jsonrpc::{self, simple_http::SimpleHttpTransport},
+    Auth,
+  },
+};
 
 #[derive(Clone, Default, Debug, Parser)]
 #[command(group(
@@ -13,6 +19,22 @@ pub(crate) struct Options {
   pub(crate) bitcoin_rpc_pass: Option<String>,
   #[arg(long, help = "Authenticate to Bitcoin Core RPC as <RPC_USER>.")]
   pub(crate) bitcoin_rpc_user: Option<String>,
+  #[arg(
+    long,
+    default_value = "64",
+    help = "Keep up to <BLOCK_CACHE_SIZE> MiB of recently fetched blocks in memory."
+  )]
+  pub(crate) block_cache_size: usize,
+  #[arg(
+    long,
+    help = "Read blocks from Bitcoin Core block files in <BLOCKS_DIR> instead of over RPC, where available."
+  )]
+  pub(crate) blocks_dir: Option<PathBuf>,
+  #[arg(
+    long,
+    help = "Flush sat ranges to the index file when the in-memory cache exceeds <CACHE_SIZE> MiB."
+  )]
+  pub(crate) cache_size: Option<usize>,
   #[arg(
     long = "chain",
     value_enum,
@@ -20,6 +42,13 @@ pub(crate) struct Options {
     help = "Use <CHAIN>."
   )]
   pub(crate) chain_argument: Chain,
+  #[arg(
+    long,
+    default_value = "5000",
+    value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..),
+    help = "Commit changes to the index file every <COMMIT_INTERVAL> blocks."
+  )]
+  pub(crate) commit_interval: usize,
   #[arg(long, help = "Load configuration from <CONFIG>.")]
   pub(crate) config: Option<PathBuf>,
   #[arg(long, help = "Load configuration from <CONFIG_DIR>.")]
@@ -42,6 +71,8 @@ pub(crate) struct Options {
   pub(crate) height_limit: Option<u64>,
   #[arg(long, help = "Use index at <INDEX>.")]
   pub(crate) index: Option<PathBuf>,
+  #[arg(long, help = "Track unspent outputs of all addresses.")]
+  pub(crate) index_addresses: bool,
   #[arg(
     long,
     help = "Track location of runes. RUNES ARE IN AN UNFINISHED PRE-ALPHA STATE AND SUBJECT TO CHANGE AT ANY TIME."
@@ -49,14 +80,53 @@ pub(crate) struct Options {
   pub(crate) index_runes_pre_alpha_i_agree_to_get_rekt: bool,
   #[arg(long, help = "Track location of all satoshis.")]
   pub(crate) index_sats: bool,
+  #[arg(long, help = "Track block height at which each output was spent.")]
+  pub(crate) index_spent_outputs: bool,
+  #[arg(long, help = "Track block height of all transactions.")]
+  pub(crate) index_txids: bool,
+  #[arg(
+    long,
+    value_enum,
+    default_value = "human",
+    help = "Write log messages as <LOG_FORMAT>."
+  )]
+  pub(crate) log_format: LogFormat,
+  #[arg(
+    long,
+    help = "Stop indexing with an error instead of committing once the index file exceeds <MAX_INDEX_SIZE> bytes."
+  )]
+  pub(crate) max_index_size: Option<u64>,
+  #[arg(
+    long,
+    default_value = "32",
+    help = "Fetch up to <PREFETCH_BLOCKS> blocks ahead of the block being indexed."
+  )]
+  pub(crate) prefetch_blocks: usize,
   #[arg(long, short, help = "Use regtest. Equivalent to `--chain regtest`.")]
   pub(crate) regtest: bool,
+  #[arg(
+    long,
+    default_value = "10",
+    help = "Give up fetching a block after <RPC_MAX_RETRIES> failed attempts."
+  )]
+  pub(crate) rpc_max_retries: u32,
+  #[arg(
+    long,
+    default_value = "30",
+    help = "Time out Bitcoin Core RPC calls fetching blocks after <RPC_TIMEOUT_SECONDS> seconds."
+  )]
+  pub(crate) rpc_timeout_seconds: u64,
   #[arg(long, help = "Connect to Bitcoin Core RPC at <RPC_URL>.")]
   pub(crate) rpc_url: Option<String>,
   #[arg(long, short, help = "Use signet. Equivalent to `--chain signet`.")]
   pub(crate) signet: bool,
   #[arg(long, short, help = "Use testnet. Equivalent to `--chain testnet`.")]
   pub(crate) testnet: bool,
+  #[arg(
+    long,
+    help = "Log a warning when committing to an index file larger than <WARN_INDEX_SIZE> bytes."
+  )]
+  pub(crate) warn_index_size: Option<u64>,
   #[arg(long, default_value = "ord", help = "Use wallet named <WALLET>.")]
   pub(crate) wallet: String,
   #[arg(long, short = 'j', help = "Enable JSON API.")]
@@ -92,18 +162,88 @@ impl Options {
     self.index_runes_pre_alpha_i_agree_to_get_rekt && self.chain() != Chain::Mainnet
   }
 
+  /// `Client::new`, but with `timeout`, if given, applied to each RPC call
+  /// instead of the transport's default.
+  pub(crate) fn connect(rpc_url: &str, auth: Auth, timeout: Option<Duration>) -> Result<Client> {
+    let (user, pass) = auth.get_user_pass()?;
+
+    let mut builder = SimpleHttpTransport::builder().url(rpc_url)?;
+
+    if let Some(timeout) = timeout {
+      builder = builder.timeout(timeout);
+    }
+
+    if let Some(user) = user {
+      builder = builder.auth(user, pass);
+    }
+
+    Ok(Client::from_jsonrpc(jsonrpc::Client::with_transport(
+      builder.build(),
+    )))
+  }
+
   pub(crate) fn rpc_url(&self) -> String {
     if let Some(rpc_url) = &self.rpc_url {
       format!("{rpc_url}/wallet/{}", self.wallet)
     } else {
       format!(
         "127.0.0.1:{}/wallet/{}",
-        self.chain().default_rpc_port(),
+        self
+          .bitcoin_conf_rpc_port()
+          .unwrap_or_else(|| self.chain().default_rpc_port()),
         self.wallet
       )
     }
   }
 
+  fn bitcoin_conf_rpc_port(&self) -> Option<u16> {
+    let conf = fs::read_to_string(self.bitcoin_data_dir.as_ref()?.join("bitcoin.conf")).ok()?;
+
+    let chain = match self.chain() {
+      Chain::Mainnet => "main",
+      Chain::Regtest => "regtest",
+      Chain::Signet => "signet",
+      Chain::Testnet => "test",
+    };
+
+    // like Bitcoin Core, only apply an rpcport outside of a network section
+    // to mainnet
+    let mut default = None;
+    let mut network = None;
+    let mut section = None;
+
+    for line in conf.lines() {
+      let line = line.split('#').next().unwrap_or_default().trim();
+
+      if let Some(name) = line
+        .strip_prefix('[')
+        .and_then(|line| line.strip_suffix(']'))
+      {
+        section = Some(name.trim().to_owned());
+        continue;
+      }
+
+      let Some((key, value)) = line.split_once('=') else {
+        continue;
+      };
+
+      let Ok(port) = value.trim().parse::<u16>() else {
+        continue;
+      };
+
+      let key = key.trim();
+
+      match &section {
+        Some(name) if name == chain && key == "rpcport" => network = Some(port),
+        None if key == format!("{chain}.rpcport") => network = Some(port),
+        None if key == "rpcport" && self.chain() == Chain::Mainnet => default = Some(port),
+        _ => {}
+      }
+    }
+
+    network.or(default)
+  }
+
   pub(crate) fn cookie_file(&self) -> Result<PathBuf> {
     if let Some(cookie_file) = &self.cookie_file {
       return Ok(cookie_file.clone());
@@ -158,7 +298,7 @@ impl Options {
     )
   }
 
-  fn derive_var(
+  pub(crate) fn derive_var(
     arg_value: Option<&str>,
     env_key: Option<&str>,
     config_value: Option<&str>,
@@ -221,7 +361,7 @@ impl Options {
       );
     }
 
-    let client = Client::new(&rpc_url, auth)
+    let client = Self::connect(&rpc_url, auth, None)
       .with_context(|| format!("failed to connect to Bitcoin Core RPC at {rpc_url}"))?;
 
     let rpc_chain = match client.get_blockchain_info()?.chain.as_str() {
@@ -285,6 +425,29 @@ impl Options {
 mod tests {
   use {super::*, bitcoin::Network, std::path::Path};
 
+  #[test]
+  fn rpc_timeout_and_max_retries() {
+    let options = Arguments::try_parse_from(["ord", "index", "update"])
+      .unwrap()
+      .options;
+
+    assert_eq!(options.rpc_timeout_seconds, 30);
+    assert_eq!(options.rpc_max_retries, 10);
+
+    let options = Arguments::try_parse_from([
+      "ord",
+      "--rpc-timeout-seconds=5",
+      "--rpc-max-retries=2",
+      "index",
+      "update",
+    ])
+    .unwrap()
+    .options;
+
+    assert_eq!(options.rpc_timeout_seconds, 5);
+    assert_eq!(options.rpc_max_retries, 2);
+  }
+
   #[test]
   fn rpc_url_overrides_network() {
     assert_eq!(
@@ -418,6 +581,37 @@ mod tests {
     }));
   }
 
+  #[test]
+  fn rpc_port_is_read_from_bitcoin_conf() {
+    let tempdir = TempDir::new().unwrap();
+
+    fs::write(
+      tempdir.path().join("bitcoin.conf"),
+      "rpcport=1000 # mainnet\ntest.rpcport=2000\n[regtest]\nrpcport = 3000\n",
+    )
+    .unwrap();
+
+    let rpc_url = |chain: &str| {
+      Arguments::try_parse_from([
+        "ord",
+        "--bitcoin-data-dir",
+        tempdir.path().to_str().unwrap(),
+        "--chain",
+        chain,
+        "index",
+        "update",
+      ])
+      .unwrap()
+      .options
+      .rpc_url()
+    };
+
+    assert_eq!(rpc_url("mainnet"), "127.0.0.1:1000/wallet/ord");
+    assert_eq!(rpc_url("testnet"), "127.0.0.1:2000/wallet/ord");
+    assert_eq!(rpc_url("regtest"), "127.0.0.1:3000/wallet/ord");
+    assert_eq!(rpc_url("signet"), "127.0.0.1:38332/wallet/ord");
+  }
+
   #[test]
   fn mainnet_data_dir() {
     let data_dir = Arguments::try_parse_from(["ord", "index", "update"])
@@ -801,6 +995,33 @@ mod tests {
     assert_eq!(arguments.options.db_cache_size, Some(16000000000));
   }
 
+  #[test]
+  fn setting_cache_size() {
+    let arguments =
+      Arguments::try_parse_from(["ord", "--cache-size", "512", "index", "update"]).unwrap();
+    assert_eq!(arguments.options.cache_size, Some(512));
+  }
+
+  #[test]
+  fn setting_commit_interval() {
+    let arguments =
+      Arguments::try_parse_from(["ord", "--commit-interval", "500", "index", "update"]).unwrap();
+    assert_eq!(arguments.options.commit_interval, 500);
+  }
+
+  #[test]
+  fn commit_interval_defaults_to_5000() {
+    let arguments = Arguments::try_parse_from(["ord", "index", "update"]).unwrap();
+    assert_eq!(arguments.options.commit_interval, 5000);
+  }
+
+  #[test]
+  fn commit_interval_must_not_be_zero() {
+    assert!(
+      Arguments::try_parse_from(["ord", "--commit-interval", "0", "index", "update"]).is_err()
+    );
+  }
+
   #[test]
   fn index_runes_only_returns_true_if_index_runes_flag_is_passed_and_not_on_mainnnet() {
     assert!(Arguments::try_parse_from([
@@ -33,7 +33,7 @@ impl Sat {
   }
 
   pub(crate) fn period(self) -> u64 {
-    self.height().n() / DIFFCHANGE_INTERVAL
+    self.height().period()
   }
 
   pub(crate) fn third(self) -> u64 {
@@ -375,6 +375,19 @@ mod tests {
     assert_eq!(Sat(2099999997689999).epoch(), 32);
   }
 
+  #[test]
+  fn epoch_agrees_with_height_epoch() {
+    for epoch in 0..Epoch::FIRST_POST_SUBSIDY.0 {
+      let first = Epoch(epoch).starting_sat();
+      let last = Sat(Epoch(epoch + 1).starting_sat().n() - 1);
+      assert_eq!(first.epoch(), epoch);
+      assert_eq!(last.epoch(), epoch);
+      assert_eq!(Epoch::from(first.height()), first.epoch());
+      assert_eq!(Epoch::from(last.height()), last.epoch());
+      assert_eq!(first.height(), Epoch(epoch).starting_height());
+    }
+  }
+
   #[test]
   fn epoch_position() {
     assert_eq!(Epoch(0).starting_sat().epoch_position(), 0);
@@ -546,6 +559,22 @@ mod tests {
     assert!(parse("aaaaaaaaaaaaaaaaaaaaaaaaaaaaaa").is_err());
   }
 
+  #[test]
+  fn name_round_trip() {
+    fn case(n: u64) {
+      let expected = Sat(n);
+      let actual = expected.name().parse::<Sat>().unwrap();
+      assert_eq!(expected, actual);
+    }
+
+    for n in 0..1024 {
+      case(n);
+      case(Sat::LAST.n() / 2 + n);
+      case(Sat::LAST.n() - n);
+      case(Sat::LAST.n() / (n + 1));
+    }
+  }
+
   #[test]
   fn cycle() {
     assert_eq!(
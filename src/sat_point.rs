@@ -97,6 +97,48 @@ mod tests {
       .unwrap_err();
   }
 
+  #[test]
+  fn ordering() {
+    let outpoint = |txid: u8, vout| OutPoint {
+      txid: Txid::from_byte_array([txid; 32]),
+      vout,
+    };
+
+    let mut satpoints = [
+      SatPoint {
+        outpoint: outpoint(1, 0),
+        offset: 1,
+      },
+      SatPoint {
+        outpoint: outpoint(0, 1),
+        offset: 0,
+      },
+      SatPoint {
+        outpoint: outpoint(1, 0),
+        offset: 0,
+      },
+      SatPoint {
+        outpoint: outpoint(0, 0),
+        offset: 2,
+      },
+    ];
+
+    satpoints.sort();
+
+    assert_eq!(
+      satpoints
+        .iter()
+        .map(|satpoint| (satpoint.outpoint, satpoint.offset))
+        .collect::<Vec<(OutPoint, u64)>>(),
+      [
+        (outpoint(0, 0), 2),
+        (outpoint(0, 1), 0),
+        (outpoint(1, 0), 0),
+        (outpoint(1, 0), 1),
+      ]
+    );
+  }
+
   #[test]
   fn deserialize_ok() {
     assert_eq!(
@@ -1,13 +1,16 @@
 use super::*;
 
+pub mod balance;
 pub mod decode;
 pub mod epochs;
 pub mod find;
 mod index;
 pub mod info;
+pub mod inscriptions;
 pub mod list;
 pub mod parse;
 mod preview;
+pub mod sat;
 mod server;
 pub mod subsidy;
 pub mod supply;
@@ -16,6 +19,8 @@ pub mod wallet;
 
 #[derive(Debug, Parser)]
 pub(crate) enum Subcommand {
+  #[command(about = "List the sats held by an address")]
+  Balance(balance::Balance),
   #[command(about = "Decode a transaction")]
   Decode(decode::Decode),
   #[command(about = "List the first satoshis of each reward epoch")]
@@ -26,12 +31,16 @@ pub(crate) enum Subcommand {
   Index(index::IndexSubcommand),
   #[command(about = "Display index statistics")]
   Info(info::Info),
+  #[command(about = "List inscriptions created in a range of blocks as JSON Lines")]
+  Inscriptions(inscriptions::Inscriptions),
   #[command(about = "List the satoshis in an output")]
   List(list::List),
   #[command(about = "Parse a satoshi from ordinal notation")]
   Parse(parse::Parse),
   #[command(about = "Run an explorer server populated with inscriptions")]
   Preview(preview::Preview),
+  #[command(about = "Display a satoshi's traits, location, and inscriptions")]
+  Sat(sat::SatCommand),
   #[command(about = "Run the explorer server")]
   Server(server::Server),
   #[command(about = "Display information about a block's subsidy")]
@@ -47,14 +56,17 @@ pub(crate) enum Subcommand {
 impl Subcommand {
   pub(crate) fn run(self, options: Options) -> SubcommandResult {
     match self {
+      Self::Balance(balance) => balance.run(options),
       Self::Decode(decode) => decode.run(),
       Self::Epochs => epochs::run(),
       Self::Find(find) => find.run(options),
       Self::Index(index) => index.run(options),
       Self::Info(info) => info.run(options),
+      Self::Inscriptions(inscriptions) => inscriptions.run(options),
       Self::List(list) => list.run(options),
       Self::Parse(parse) => parse.run(),
       Self::Preview(preview) => preview.run(),
+      Self::Sat(sat) => sat.run(options),
       Self::Server(server) => {
         let index = Arc::new(Index::open(&options)?);
         let handle = axum_server::Handle::new();
@@ -76,6 +88,13 @@ pub(crate) trait Output: Send {
   fn print_json(&self);
 }
 
+/// Returned by subcommands that have already written their output to stdout.
+pub(crate) struct Streamed;
+
+impl Output for Streamed {
+  fn print_json(&self) {}
+}
+
 impl<T> Output for T
 where
   T: Serialize + Send,
@@ -0,0 +1,107 @@
+use {super::*, bitcoincore_rpc::json::ScanTxOutRequest};
+
+#[derive(Debug, Parser)]
+pub(crate) struct Balance {
+  #[arg(help = "List sats held by <ADDRESS>.")]
+  address: Address<NetworkUnchecked>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Output {
+  pub output: OutPoint,
+  pub value: u64,
+  pub ranges: Vec<(u64, u64)>,
+  pub rare_sats: Vec<RareSat>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct RareSat {
+  pub sat: Sat,
+  pub offset: u64,
+  pub rarity: Rarity,
+}
+
+impl Balance {
+  pub(crate) fn run(self, options: Options) -> SubcommandResult {
+    let address = self.address.require_network(options.chain().network())?;
+
+    let index = Index::open(&options)?;
+
+    if !index.has_sat_index() {
+      bail!("balance requires index created with `--index-sats` flag");
+    }
+
+    index.update()?;
+
+    let mut outpoints = if index.has_address_index() {
+      index.outpoints_for_address(&address)?
+    } else {
+      // scantxoutset walks the whole UTXO set, which takes minutes on mainnet
+      Options::connect(&options.rpc_url(), options.auth()?, Some(Duration::MAX))?
+        .scan_tx_out_set_blocking(&[ScanTxOutRequest::Single(format!("addr({address})"))])?
+        .unspents
+        .into_iter()
+        .map(|utxo| OutPoint::new(utxo.txid, utxo.vout))
+        .collect()
+    };
+
+    outpoints.sort();
+
+    let mut outputs = Vec::new();
+    for outpoint in outpoints {
+      let Some(crate::index::List::Unspent(ranges)) = index.list(outpoint)? else {
+        bail!("output {outpoint} not found in index");
+      };
+
+      outputs.push(Output {
+        output: outpoint,
+        value: ranges.iter().map(|(start, end)| end - start).sum(),
+        rare_sats: rare_sats(&ranges),
+        ranges,
+      });
+    }
+
+    Ok(Box::new(outputs))
+  }
+}
+
+fn rare_sats(ranges: &[(u64, u64)]) -> Vec<RareSat> {
+  let mut offset = 0;
+  ranges
+    .iter()
+    .filter_map(|&(start, end)| {
+      let sat = Sat(start);
+      let rare_sat = RareSat {
+        sat,
+        offset,
+        rarity: sat.rarity(),
+      };
+      offset += end - start;
+      (rare_sat.rarity > Rarity::Common).then_some(rare_sat)
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn rare_sats_are_found_at_range_starts() {
+    assert_eq!(
+      rare_sats(&[(0, 10), (11, 20), (50 * COIN_VALUE, 50 * COIN_VALUE + 1)]),
+      vec![
+        RareSat {
+          sat: Sat(0),
+          offset: 0,
+          rarity: Rarity::Mythic,
+        },
+        RareSat {
+          sat: Sat(50 * COIN_VALUE),
+          offset: 19,
+          rarity: Rarity::Uncommon,
+        },
+      ]
+    );
+  }
+}
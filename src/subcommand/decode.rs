@@ -1,12 +1,28 @@
-use super::*;
+use {
+  super::*,
+  crate::index::{assign_sat_ranges, Lot, LotQueue},
+};
 
 #[derive(Serialize, Eq, PartialEq, Deserialize, Debug)]
 pub struct Output {
   pub inscriptions: Vec<Inscription>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub sat_ranges: Option<SatRanges>,
+}
+
+#[derive(Serialize, Eq, PartialEq, Deserialize, Debug)]
+pub struct SatRanges {
+  pub outputs: Vec<Vec<(u64, u64)>>,
+  pub fee: Vec<(u64, u64)>,
 }
 
 #[derive(Debug, Parser)]
 pub(crate) struct Decode {
+  #[arg(
+    long,
+    help = "Assign sats to outputs from <INPUT_RANGES>, a JSON array of the sat ranges of each input."
+  )]
+  input_ranges: Option<String>,
   transaction: Option<PathBuf>,
 }
 
@@ -20,11 +36,53 @@ impl Decode {
 
     let inscriptions = ParsedEnvelope::from_transaction(&transaction);
 
+    let sat_ranges = self
+      .input_ranges
+      .map(|input_ranges| {
+        let input_ranges = serde_json::from_str::<Vec<Vec<(u64, u64)>>>(&input_ranges)
+          .context("invalid input ranges")?;
+
+        if input_ranges.len() != transaction.input.len() {
+          bail!(
+            "transaction has {} inputs but ranges were given for {}",
+            transaction.input.len(),
+            input_ranges.len()
+          );
+        }
+
+        for &(start, end) in input_ranges.iter().flatten() {
+          if start > end {
+            bail!("invalid input range {start}-{end}");
+          }
+        }
+
+        let mut input_sat_ranges = input_ranges
+          .into_iter()
+          .flatten()
+          .map(Lot::from)
+          .collect::<LotQueue>();
+
+        let (outputs, _splits) = assign_sat_ranges(
+          transaction.output.iter().map(|output| output.value),
+          &mut input_sat_ranges,
+        )?;
+
+        Ok(SatRanges {
+          outputs: outputs
+            .into_iter()
+            .map(|output| output.into_iter().map(Into::into).collect())
+            .collect(),
+          fee: input_sat_ranges.into_iter().map(Into::into).collect(),
+        })
+      })
+      .transpose()?;
+
     Ok(Box::new(Output {
       inscriptions: inscriptions
         .into_iter()
         .map(|inscription| inscription.payload)
         .collect(),
+      sat_ranges,
     }))
   }
 }
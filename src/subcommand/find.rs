@@ -11,6 +11,7 @@ pub(crate) struct Find {
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct Output {
   pub satpoint: SatPoint,
+  pub value: Option<u64>,
 }
 
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
@@ -36,7 +37,10 @@ impl Find {
         None => Err(anyhow!("range has not been mined as of index height")),
       },
       None => match index.find(self.sat.0)? {
-        Some(satpoint) => Ok(Box::new(Output { satpoint })),
+        Some(satpoint) => Ok(Box::new(Output {
+          satpoint,
+          value: index.get_output_value(satpoint.outpoint)?,
+        })),
         None => Err(anyhow!("sat has not been mined as of index height")),
       },
     }
@@ -1,21 +1,33 @@
 use super::*;
 
 mod export;
+mod export_sats;
 mod update;
+mod verify;
+mod verify_inscription;
 
 #[derive(Debug, Parser)]
 pub(crate) enum IndexSubcommand {
   #[command(about = "Write inscription numbers and ids to a tab-separated file")]
   Export(export::Export),
+  #[command(about = "Write the sat ranges of every output to a CSV or JSON file")]
+  ExportSats(export_sats::ExportSats),
   #[command(about = "Update the index", alias = "run")]
-  Update,
+  Update(update::Update),
+  #[command(about = "Verify that indexed block hashes form a chain")]
+  Verify(verify::Verify),
+  #[command(about = "Check an inscription's envelope against its reveal transaction")]
+  VerifyInscription(verify_inscription::VerifyInscription),
 }
 
 impl IndexSubcommand {
   pub(crate) fn run(self, options: Options) -> SubcommandResult {
     match self {
       Self::Export(export) => export.run(options),
-      Self::Update => update::run(options),
+      Self::ExportSats(export_sats) => export_sats.run(options),
+      Self::Update(update) => update.run(options),
+      Self::Verify(verify) => verify.run(options),
+      Self::VerifyInscription(verify_inscription) => verify_inscription.run(options),
     }
   }
 }
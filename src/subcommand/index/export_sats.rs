@@ -0,0 +1,105 @@
+use {
+  super::*,
+  clap::ValueEnum,
+  std::io::{self, BufWriter, Write},
+};
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum Format {
+  Csv,
+  Json,
+}
+
+#[derive(Debug, Parser)]
+pub(crate) struct ExportSats {
+  #[arg(
+    long,
+    value_enum,
+    default_value = "csv",
+    help = "Write sat ranges as <FORMAT>."
+  )]
+  format: Format,
+  #[arg(long, help = "Write sat ranges to <OUTPUT> instead of stdout.")]
+  output: Option<PathBuf>,
+}
+
+#[derive(Serialize)]
+struct Row {
+  txid: Txid,
+  vout: u32,
+  ranges: Vec<(u64, u64)>,
+}
+
+impl ExportSats {
+  pub(crate) fn run(self, options: Options) -> SubcommandResult {
+    let index = Index::open(&options)?;
+
+    if !index.has_sat_index() {
+      bail!("export-sats requires index created with `--index-sats` flag");
+    }
+
+    index.update()?;
+
+    let mut writer: BufWriter<Box<dyn Write>> = BufWriter::new(match &self.output {
+      Some(output) => Box::new(File::create(output)?),
+      None => Box::new(io::stdout().lock()),
+    });
+
+    match self.format {
+      Format::Csv => writeln!(writer, "txid,vout,ranges")?,
+      Format::Json => write!(writer, "[")?,
+    }
+
+    let mut first = true;
+
+    index.for_each_output_sat_ranges(|outpoint, ranges| {
+      // lost sats aren't in any output
+      if outpoint == OutPoint::null() {
+        return Ok(());
+      }
+
+      match self.format {
+        Format::Csv => writeln!(
+          writer,
+          "{},{},{}",
+          outpoint.txid,
+          outpoint.vout,
+          ranges
+            .iter()
+            .map(|(start, end)| format!("{start}-{end}"))
+            .collect::<Vec<String>>()
+            .join(";")
+        )?,
+        Format::Json => {
+          if !first {
+            write!(writer, ",")?;
+          }
+          serde_json::to_writer(
+            &mut writer,
+            &Row {
+              txid: outpoint.txid,
+              vout: outpoint.vout,
+              ranges,
+            },
+          )?;
+        }
+      }
+
+      first = false;
+
+      Ok(())
+    })?;
+
+    if let Format::Json = self.format {
+      writeln!(writer, "]")?;
+    }
+
+    writer.flush()?;
+
+    if self.output.is_some() {
+      Ok(Box::new(Empty {}))
+    } else {
+      Ok(Box::new(Streamed))
+    }
+  }
+}
@@ -1,9 +1,63 @@
 use super::*;
 
-pub(crate) fn run(options: Options) -> SubcommandResult {
-  let index = Index::open(&options)?;
+const RESUME_CHECK_DEPTH: u64 = 10;
 
-  index.update()?;
+#[derive(Debug, Parser)]
+pub(crate) struct Update {
+  #[arg(
+    long,
+    help = "Compact the index file after updating. Discards reorg savepoints."
+  )]
+  compact: bool,
+  #[arg(
+    long,
+    conflicts_with = "compact",
+    help = "Report how far the index is behind the chain tip without updating it. Fails if it is behind."
+  )]
+  dry_run: bool,
+  #[arg(
+    long,
+    conflicts_with = "dry_run",
+    help = "Before updating, fail if any of the last 10 indexed block hashes no longer match bitcoind's. Without this, the update resumes from the last indexed block and handles shallow reorgs by rolling back."
+  )]
+  resume: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct DryRunOutput {
+  blocks_behind: u64,
+}
+
+impl Update {
+  pub(crate) fn run(self, options: Options) -> SubcommandResult {
+    let mut index = Index::open(&options)?;
+
+    if self.dry_run {
+      let blocks_behind = index.blocks_behind()?;
+
+      if blocks_behind > 0 {
+        match index.indexing_rate()? {
+          Some(rate) => bail!(
+            "index is {blocks_behind} blocks behind, about {}s at the most recent rate of {rate:.2} blocks/s",
+            (blocks_behind as f64 / rate).ceil()
+          ),
+          None => bail!("index is {blocks_behind} blocks behind"),
+        }
+      }
+
+      return Ok(Box::new(DryRunOutput { blocks_behind }));
+    }
+
+    if self.resume {
+      index.check_recent_block_hashes(RESUME_CHECK_DEPTH)?;
+    }
+
+    index.update()?;
+
+    if self.compact {
+      index.compact()?;
+    }
 
-  Ok(Box::new(Empty {}))
+    Ok(Box::new(Empty {}))
+  }
 }
@@ -0,0 +1,50 @@
+use super::*;
+
+#[derive(Debug, Parser)]
+pub(crate) struct Verify {
+  #[arg(long, default_value = "0", help = "Start verifying at block <START>.")]
+  start: u64,
+  #[arg(long, help = "Stop verifying before block <END>.")]
+  end: Option<u64>,
+}
+
+impl Verify {
+  pub(crate) fn run(self, options: Options) -> SubcommandResult {
+    let index = Index::open(&options)?;
+
+    let end = match self.end {
+      Some(end) => end,
+      None => index.block_count()?,
+    };
+
+    let gaps = index.gaps(self.start, end)?;
+
+    if !gaps.is_empty() {
+      bail!(
+        "index is missing blocks {}",
+        gaps
+          .iter()
+          .map(|(start, end)| format!("{start}..{end}"))
+          .collect::<Vec<String>>()
+          .join(", ")
+      );
+    }
+
+    let mismatches = index.verify_checksums(self.start, end)?;
+
+    if !mismatches.is_empty() {
+      bail!(
+        "block hash checksum mismatch at heights {}",
+        mismatches
+          .iter()
+          .map(|height| height.to_string())
+          .collect::<Vec<String>>()
+          .join(", ")
+      );
+    }
+
+    index.verify_chain_continuity(self.start, end)?;
+
+    Ok(Box::new(Empty {}))
+  }
+}
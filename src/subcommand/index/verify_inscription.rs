@@ -0,0 +1,149 @@
+use {super::*, bitcoin::hashes::sha256};
+
+#[derive(Debug, Parser)]
+pub(crate) struct VerifyInscription {
+  #[arg(help = "Verify <INSCRIPTION_ID>.")]
+  inscription: InscriptionId,
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct Output {
+  inscription: InscriptionId,
+  height: u64,
+  content_type: Option<String>,
+  content_length: Option<usize>,
+  content_sha256: Option<sha256::Hash>,
+  satpoint: SatPoint,
+  fee: u64,
+  timestamp: u32,
+}
+
+impl VerifyInscription {
+  pub(crate) fn run(self, options: Options) -> SubcommandResult {
+    let index = Index::open(&options)?;
+
+    let id = self.inscription;
+
+    let entry = index
+      .get_inscription_entry(id)?
+      .ok_or_else(|| anyhow!("inscription {id} not found in index"))?;
+
+    let tx = index
+      .get_transaction(id.txid)?
+      .ok_or_else(|| anyhow!("reveal transaction {} not found", id.txid))?;
+
+    let block = index
+      .get_block_by_height(entry.height)?
+      .ok_or_else(|| anyhow!("block {} not found", entry.height))?;
+
+    let envelopes = ParsedEnvelope::from_transaction(&tx);
+
+    let inscription = envelopes
+      .get(usize::try_from(id.index).unwrap())
+      .map(|envelope| envelope.payload.clone())
+      .ok_or_else(|| {
+        anyhow!(
+          "reveal transaction {} has no envelope {}",
+          id.txid,
+          id.index
+        )
+      })?;
+
+    let mut input_value = 0;
+    for input in &tx.input {
+      let OutPoint { txid, vout } = input.previous_output;
+
+      let previous = index
+        .get_transaction(txid)?
+        .ok_or_else(|| anyhow!("input transaction {txid} not found"))?;
+
+      input_value += previous
+        .output
+        .get(usize::try_from(vout).unwrap())
+        .ok_or_else(|| anyhow!("input transaction {txid} has no output {vout}"))?
+        .value;
+    }
+
+    let output_value = tx.output.iter().map(|output| output.value).sum::<u64>();
+
+    let Some(fees) = input_value.checked_sub(output_value) else {
+      bail!(
+        "reveal transaction {} spends {input_value} sats but its outputs hold {output_value}",
+        id.txid
+      );
+    };
+
+    let fee = fees / u64::try_from(envelopes.len()).unwrap();
+
+    let content_sha256 = inscription.body().map(sha256::Hash::hash);
+
+    let satpoint = index
+      .get_inscription_satpoint_by_id(id)?
+      .ok_or_else(|| anyhow!("inscription {id} has no satpoint in index"))?;
+
+    let mut mismatches = Vec::new();
+
+    if !block.txdata.iter().any(|tx| tx.txid() == id.txid) {
+      mismatches.push(format!(
+        "height: index has {}, but reveal transaction {} is not in that block",
+        entry.height, id.txid
+      ));
+    }
+
+    if satpoint.outpoint.txid == id.txid
+      && usize::try_from(satpoint.outpoint.vout).unwrap() >= tx.output.len()
+    {
+      mismatches.push(format!(
+        "satpoint: index has {satpoint}, but reveal transaction has {} outputs",
+        tx.output.len()
+      ));
+    }
+
+    if entry.fee != fee {
+      mismatches.push(format!(
+        "fee: index has {}, reveal transaction has {fee}",
+        entry.fee
+      ));
+    }
+
+    if entry.timestamp != block.header.time {
+      mismatches.push(format!(
+        "timestamp: index has {}, block {} has {}",
+        entry.timestamp, entry.height, block.header.time
+      ));
+    }
+
+    if let Some(content_sha256) = content_sha256 {
+      if !index
+        .inscriptions_with_content_hash(content_sha256.to_byte_array())?
+        .contains(&id)
+      {
+        mismatches.push(format!(
+          "content hash: index has no inscription {id} with content hash {content_sha256}"
+        ));
+      }
+    }
+
+    if !mismatches.is_empty() {
+      bail!(
+        "inscription {id} does not match index:\n{}",
+        mismatches
+          .iter()
+          .map(|mismatch| format!("  {mismatch}"))
+          .collect::<Vec<String>>()
+          .join("\n")
+      );
+    }
+
+    Ok(Box::new(Output {
+      inscription: id,
+      height: entry.height,
+      content_type: inscription.content_type().map(str::to_owned),
+      content_length: inscription.content_length(),
+      content_sha256,
+      satpoint,
+      fee: entry.fee,
+      timestamp: entry.timestamp,
+    }))
+  }
+}
@@ -0,0 +1,109 @@
+use {
+  super::*,
+  std::{
+    collections::hash_map,
+    io::{self, BufWriter, Write},
+  },
+};
+
+#[derive(Debug, Parser)]
+pub(crate) struct Inscriptions {
+  #[arg(long, help = "List inscriptions created at or above <FROM_HEIGHT>.")]
+  from_height: u64,
+  #[arg(long, help = "List inscriptions created below <TO_HEIGHT>.")]
+  to_height: u64,
+  #[arg(
+    long,
+    help = "Only list inscriptions whose content type starts with <CONTENT_TYPE>."
+  )]
+  content_type: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Output {
+  pub inscription: InscriptionId,
+  pub number: i64,
+  pub height: u64,
+  pub sat: Option<Sat>,
+  pub content_type: Option<String>,
+  pub content_length: Option<usize>,
+}
+
+impl Inscriptions {
+  pub(crate) fn run(self, options: Options) -> SubcommandResult {
+    if self.to_height < self.from_height {
+      bail!("to height is below from height");
+    }
+
+    let index = Index::open(&options)?;
+
+    index.update()?;
+
+    let mut writer = BufWriter::new(io::stdout().lock());
+
+    for height in self.from_height..self.to_height {
+      let entries = index.get_inscription_entries_in_block(height)?;
+
+      if entries.is_empty() {
+        continue;
+      }
+
+      let block = index
+        .get_block_by_height(height)?
+        .ok_or_else(|| anyhow!("block {height} not found"))?;
+
+      let mut transactions = block
+        .txdata
+        .iter()
+        .map(|tx| (tx.txid(), tx))
+        .collect::<HashMap<Txid, &Transaction>>();
+
+      let mut parsed = HashMap::<Txid, Vec<ParsedEnvelope>>::new();
+
+      for (id, entry) in entries {
+        let envelopes = match parsed.entry(id.txid) {
+          hash_map::Entry::Occupied(entry) => entry.into_mut(),
+          hash_map::Entry::Vacant(entry) => {
+            let tx = transactions
+              .remove(&id.txid)
+              .ok_or_else(|| anyhow!("reveal transaction {} not in block {height}", id.txid))?;
+            entry.insert(ParsedEnvelope::from_transaction(tx))
+          }
+        };
+
+        let inscription = envelopes
+          .get(usize::try_from(id.index).unwrap())
+          .map(|envelope| &envelope.payload)
+          .ok_or_else(|| anyhow!("inscription {id} not found"))?;
+
+        if let Some(content_type) = &self.content_type {
+          if !inscription
+            .content_type()
+            .map(|actual| actual.starts_with(content_type.as_str()))
+            .unwrap_or_default()
+          {
+            continue;
+          }
+        }
+
+        serde_json::to_writer(
+          &mut writer,
+          &Output {
+            inscription: id,
+            number: entry.inscription_number,
+            height: entry.height,
+            sat: entry.sat,
+            content_type: inscription.content_type().map(str::to_owned),
+            content_length: inscription.content_length(),
+          },
+        )?;
+
+        writeln!(writer)?;
+      }
+    }
+
+    writer.flush()?;
+
+    Ok(Box::new(Streamed))
+  }
+}
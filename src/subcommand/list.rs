@@ -53,7 +53,16 @@ impl List {
 
         Ok(Box::new(outputs))
       }
-      Some(crate::index::List::Spent) => Err(anyhow!("output spent.")),
+      Some(crate::index::List::Spent) => {
+        if index.has_spent_output_index() {
+          if let Some(height) = index.spent_height(self.outpoint)? {
+            bail!("output spent in block {height}.");
+          }
+        }
+
+        Err(anyhow!("output spent."))
+      }
+      Some(crate::index::List::Unconfirmed) => Err(anyhow!("output not yet confirmed")),
       None => Err(anyhow!("output not found")),
     }
   }
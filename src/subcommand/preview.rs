@@ -46,6 +46,11 @@ impl Preview {
       data_dir: Some(tmpdir.path().into()),
       rpc_url: Some(format!("127.0.0.1:{rpc_port}")),
       index_sats: true,
+      block_cache_size: 64,
+      commit_interval: 5000,
+      prefetch_blocks: 32,
+      rpc_max_retries: 10,
+      rpc_timeout_seconds: 30,
       ..Options::default()
     };
 
@@ -0,0 +1,53 @@
+use super::*;
+
+#[derive(Debug, Parser)]
+pub(crate) struct SatCommand {
+  #[arg(help = "Show traits and location of <SAT>.")]
+  sat: Sat,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Output {
+  pub number: u64,
+  pub name: String,
+  pub rarity: Rarity,
+  pub epoch: u64,
+  pub block: u64,
+  pub offset: u64,
+  pub satpoint: Option<SatPoint>,
+  pub inscriptions: Vec<InscriptionId>,
+  pub transfers: Option<Vec<SatPoint>>,
+}
+
+impl SatCommand {
+  pub(crate) fn run(self, options: Options) -> SubcommandResult {
+    let index = Index::open(&options)?;
+
+    index.update()?;
+
+    let sat = self.sat;
+
+    let inscriptions = index.get_inscription_ids_by_sat(sat)?;
+
+    let satpoint = match index.rare_sat_satpoint(sat)? {
+      Some(satpoint) => Some(satpoint),
+      None if index.has_sat_index() => index.find(sat.n())?,
+      None => match inscriptions.first() {
+        Some(&inscription_id) => index.get_inscription_satpoint_by_id(inscription_id)?,
+        None => None,
+      },
+    };
+
+    Ok(Box::new(Output {
+      number: sat.n(),
+      name: sat.name(),
+      rarity: index.rarity(sat)?,
+      epoch: sat.epoch().0,
+      block: sat.height().0,
+      offset: sat.third(),
+      satpoint,
+      inscriptions,
+      transfers: index.rare_sat_history(sat)?,
+    }))
+  }
+}
@@ -6,26 +6,33 @@ use {
   },
   super::*,
   crate::{
+    index::{BlockEvent, InscriptionEvent, Statistic},
     page_config::PageConfig,
     runes::Rune,
     templates::{
-      BlockHtml, ClockSvg, HomeHtml, InputHtml, InscriptionHtml, InscriptionJson,
-      InscriptionsBlockHtml, InscriptionsHtml, InscriptionsJson, OutputHtml, OutputJson,
-      PageContent, PageHtml, PreviewAudioHtml, PreviewCodeHtml, PreviewImageHtml,
+      BlockHtml, BlockJson, ClockSvg, HomeHtml, InputHtml, InscriptionHtml, InscriptionJson,
+      InscriptionsBlockHtml, InscriptionsHtml, InscriptionsJson, InscriptionsPageJson, OutputHtml,
+      OutputJson, PageContent, PageHtml, PreviewAudioHtml, PreviewCodeHtml, PreviewImageHtml,
       PreviewMarkdownHtml, PreviewModelHtml, PreviewPdfHtml, PreviewTextHtml, PreviewUnknownHtml,
       PreviewVideoHtml, RangeHtml, RareTxt, RuneHtml, RunesHtml, SatHtml, SatJson, TransactionHtml,
     },
   },
   axum::{
     body,
-    extract::{Extension, Json, Path, Query},
+    extract::{Extension, Json, Path, Query, State},
     headers::UserAgent,
-    http::{header, HeaderMap, HeaderValue, StatusCode, Uri},
-    response::{IntoResponse, Redirect, Response},
+    http::{header, HeaderMap, HeaderValue, Request, StatusCode, Uri},
+    middleware::{self, Next},
+    response::{
+      sse::{self, Sse},
+      IntoResponse, Redirect, Response,
+    },
     routing::get,
     Router, TypedHeader,
   },
-  axum_server::Handle,
+  axum_server::{tls_rustls::RustlsConfig, Handle},
+  bitcoin::hashes::sha256,
+  futures::stream::{self, Stream},
   rust_embed::RustEmbed,
   rustls_acme::{
     acme::{LETS_ENCRYPT_PRODUCTION_DIRECTORY, LETS_ENCRYPT_STAGING_DIRECTORY},
@@ -34,6 +41,7 @@ use {
     AcmeConfig,
   },
   std::{cmp::Ordering, str, sync::Arc},
+  tokio::sync::broadcast::error::RecvError,
   tokio_stream::StreamExt,
   tower_http::{
     compression::CompressionLayer,
@@ -48,6 +56,7 @@ mod error;
 #[derive(Clone)]
 pub struct ServerConfig {
   pub is_json_api_enabled: bool,
+  pub max_content_preview_size: Option<usize>,
 }
 
 enum InscriptionQuery {
@@ -88,6 +97,14 @@ enum SpawnConfig {
   Https(AxumAcceptor),
   Http,
   Redirect(String),
+  Tls(RustlsConfig),
+}
+
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum InscriptionStreamEvent {
+  Sync { current_height: Option<u64> },
+  Inscription(InscriptionEvent),
 }
 
 #[derive(Deserialize)]
@@ -95,6 +112,22 @@ struct Search {
   query: String,
 }
 
+#[derive(Deserialize)]
+struct Pagination {
+  page: Option<u64>,
+  page_size: Option<usize>,
+  cursor: Option<u64>,
+}
+
+impl Pagination {
+  const DEFAULT_PAGE_SIZE: usize = 100;
+  const MAX_PAGE_SIZE: usize = 10_000;
+
+  fn is_empty(&self) -> bool {
+    self.page.is_none() && self.page_size.is_none() && self.cursor.is_none()
+  }
+}
+
 #[derive(RustEmbed)]
 #[folder = "static"]
 struct StaticAssets;
@@ -116,6 +149,12 @@ impl Display for StaticHtml {
   }
 }
 
+/// Compare without short-circuiting, so response timing doesn't reveal how
+/// much of a token matched.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+  a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (a, b)| acc | (a ^ b)) == 0
+}
+
 #[derive(Debug, Parser)]
 pub(crate) struct Server {
   #[arg(
@@ -144,6 +183,38 @@ pub(crate) struct Server {
   acme_cache: Option<PathBuf>,
   #[arg(long, help = "Provide ACME contact <ACME_CONTACT>.")]
   acme_contact: Vec<String>,
+  #[arg(
+    long,
+    requires = "tls_key",
+    help = "Serve HTTPS using the PEM certificate chain in <TLS_CERT> instead of requesting a certificate via ACME."
+  )]
+  tls_cert: Option<PathBuf>,
+  #[arg(
+    long,
+    requires = "tls_cert",
+    help = "Load the PEM private key for --tls-cert from <TLS_KEY>."
+  )]
+  tls_key: Option<PathBuf>,
+  #[arg(
+    long,
+    help = "Only allow cross-origin requests from <CORS_ORIGIN>. May be repeated. [default: any origin]"
+  )]
+  cors_origin: Vec<String>,
+  #[arg(
+    long,
+    help = "Let browsers cache CORS preflight responses for <CORS_MAX_AGE> seconds."
+  )]
+  cors_max_age: Option<u64>,
+  #[arg(
+    long,
+    help = "Require requests to send `Authorization: Bearer <AUTH_TOKEN>`. The ORD_AUTH_TOKEN environment variable takes precedence over this flag."
+  )]
+  auth_token: Option<String>,
+  #[arg(
+    long,
+    help = "Don't serve output previews of inscriptions larger than <MAX_CONTENT_PREVIEW_SIZE> bytes."
+  )]
+  max_content_preview_size: Option<usize>,
   #[arg(long, help = "Serve HTTP traffic on <HTTP_PORT>.")]
   http: bool,
   #[arg(long, help = "Serve HTTPS traffic on <HTTPS_PORT>.")]
@@ -170,6 +241,7 @@ impl Server {
 
       let server_config = Arc::new(ServerConfig {
         is_json_api_enabled: index.is_json_api_enabled(),
+        max_content_preview_size: self.max_content_preview_size,
       });
 
       let config = options.load_config()?;
@@ -188,6 +260,7 @@ impl Server {
         .route("/blockheight", get(Self::block_height))
         .route("/blockhash", get(Self::block_hash))
         .route("/blockhash/:height", get(Self::block_hash_from_height))
+        .route("/blocks/events", get(Self::block_events))
         .route("/blocktime", get(Self::block_time))
         .route("/bounties", get(Self::bounties))
         .route("/clock", get(Self::clock))
@@ -206,11 +279,22 @@ impl Server {
           "/inscriptions/block/:height/:page_index",
           get(Self::inscriptions_in_block_from_page),
         )
+        .route(
+          "/inscriptions/address/:address",
+          get(Self::inscriptions_for_address),
+        )
+        .route("/inscriptions/events", get(Self::inscription_events))
+        .route(
+          "/inscriptions/sha256/:hash",
+          get(Self::inscriptions_with_content_hash),
+        )
         .route("/inscriptions/:from", get(Self::inscriptions_from))
         .route("/inscriptions/:from/:n", get(Self::inscriptions_from_n))
         .route("/install.sh", get(Self::install_script))
+        .route("/metrics", get(Self::metrics))
         .route("/ordinal/:sat", get(Self::ordinal))
         .route("/output/:output", get(Self::output))
+        .route("/output/:txid/:vout/preview", get(Self::output_preview))
         .route("/preview/:inscription_id", get(Self::preview))
         .route("/range/:start/:end", get(Self::range))
         .route("/rare.txt", get(Self::rare_txt))
@@ -221,7 +305,34 @@ impl Server {
         .route("/search/*query", get(Self::search_by_path))
         .route("/static/*path", get(Self::static_asset))
         .route("/status", get(Self::status))
-        .route("/tx/:txid", get(Self::transaction))
+        .route("/tx/:txid", get(Self::transaction));
+
+      let router = match self.auth_token()? {
+        Some(auth_token) => {
+          let expected = format!("Bearer {auth_token}");
+          router.route_layer(middleware::from_fn(
+            move |request: Request<body::Body>, next: Next<body::Body>| {
+              let authorized = request
+                .headers()
+                .get(header::AUTHORIZATION)
+                .map(|value| constant_time_eq(value.as_bytes(), expected.as_bytes()))
+                .unwrap_or_default();
+
+              async move {
+                if authorized {
+                  next.run(request).await
+                } else {
+                  StatusCode::UNAUTHORIZED.into_response()
+                }
+              }
+            },
+          ))
+        }
+        None => router,
+      };
+
+      let router = router
+        .route("/health", get(Self::health))
         .layer(Extension(index))
         .layer(Extension(page_config))
         .layer(Extension(Arc::new(config)))
@@ -233,11 +344,7 @@ impl Server {
           header::STRICT_TRANSPORT_SECURITY,
           HeaderValue::from_static("max-age=31536000; includeSubDomains; preload"),
         ))
-        .layer(
-          CorsLayer::new()
-            .allow_methods([http::Method::GET])
-            .allow_origin(Any),
-        )
+        .layer(self.cors_layer()?)
         .layer(CompressionLayer::new())
         .with_state(server_config);
 
@@ -253,7 +360,7 @@ impl Server {
               router,
               handle,
               https_port,
-              SpawnConfig::Https(self.acceptor(&options)?),
+              self.https_spawn_config(&options).await?,
             )?
             .await??
         }
@@ -268,14 +375,11 @@ impl Server {
             SpawnConfig::Http
           };
 
+          let https_spawn_config = self.https_spawn_config(&options).await?;
+
           let (http_result, https_result) = tokio::join!(
             self.spawn(router.clone(), handle.clone(), http_port, http_spawn_config)?,
-            self.spawn(
-              router,
-              handle,
-              https_port,
-              SpawnConfig::Https(self.acceptor(&options)?),
-            )?
+            self.spawn(router, handle, https_port, https_spawn_config)?
           );
           http_result.and(https_result)??;
         }
@@ -302,7 +406,7 @@ impl Server {
       eprintln!(
         "Listening on {}://{addr}",
         match config {
-          SpawnConfig::Https(_) => "https",
+          SpawnConfig::Https(_) | SpawnConfig::Tls(_) => "https",
           _ => "http",
         }
       );
@@ -334,6 +438,12 @@ impl Server {
             .serve(router.into_make_service())
             .await
         }
+        SpawnConfig::Tls(config) => {
+          axum_server::bind_rustls(addr, config)
+            .handle(handle)
+            .serve(router.into_make_service())
+            .await
+        }
       }
     }))
   }
@@ -359,21 +469,77 @@ impl Server {
   }
 
   fn http_port(&self) -> Option<u16> {
-    if self.http || self.http_port.is_some() || (self.https_port.is_none() && !self.https) {
+    if self.http
+      || self.http_port.is_some()
+      || (self.https_port.is_none() && !self.https && self.tls_cert.is_none())
+    {
       Some(self.http_port.unwrap_or(80))
     } else {
       None
     }
   }
 
+  fn auth_token(&self) -> Result<Option<String>> {
+    let auth_token = Options::derive_var(None, Some("AUTH_TOKEN"), None, None)?
+      .or_else(|| self.auth_token.clone());
+
+    if let Some(auth_token) = &auth_token {
+      if HeaderValue::from_str(&format!("Bearer {auth_token}")).is_err() {
+        bail!("auth token is not a valid header value");
+      }
+    }
+
+    Ok(auth_token)
+  }
+
+  fn cors_layer(&self) -> Result<CorsLayer> {
+    let cors = CorsLayer::new().allow_methods([http::Method::GET]);
+
+    let cors = if self.cors_origin.is_empty() {
+      cors.allow_origin(Any)
+    } else {
+      cors.allow_origin(
+        self
+          .cors_origin
+          .iter()
+          .map(|origin| {
+            HeaderValue::from_str(origin).with_context(|| format!("invalid CORS origin `{origin}`"))
+          })
+          .collect::<Result<Vec<HeaderValue>>>()?,
+      )
+    };
+
+    Ok(match self.cors_max_age {
+      Some(max_age) => cors.max_age(Duration::from_secs(max_age)),
+      None => cors,
+    })
+  }
+
   fn https_port(&self) -> Option<u16> {
-    if self.https || self.https_port.is_some() {
+    if self.https || self.https_port.is_some() || self.tls_cert.is_some() {
       Some(self.https_port.unwrap_or(443))
     } else {
       None
     }
   }
 
+  async fn https_spawn_config(&self, options: &Options) -> Result<SpawnConfig> {
+    Ok(match (&self.tls_cert, &self.tls_key) {
+      (Some(cert), Some(key)) => SpawnConfig::Tls(
+        RustlsConfig::from_pem_file(cert, key)
+          .await
+          .with_context(|| {
+            format!(
+              "failed to load TLS certificate `{}` and key `{}`",
+              cert.display(),
+              key.display()
+            )
+          })?,
+      ),
+      _ => SpawnConfig::Https(self.acceptor(options)?),
+    })
+  }
+
   fn acceptor(&self, options: &Options) -> Result<AxumAcceptor> {
     let config = AcmeConfig::new(self.acme_domains()?)
       .contact(&self.acme_contact)
@@ -452,7 +618,7 @@ impl Server {
         epoch: sat.epoch().0,
         period: sat.period(),
         offset: sat.third(),
-        rarity: sat.rarity(),
+        rarity: index.rarity(sat)?,
         percentile: sat.percentile(),
         satpoint,
         timestamp: blocktime.timestamp().timestamp(),
@@ -463,6 +629,7 @@ impl Server {
       SatHtml {
         sat,
         satpoint,
+        history: index.rare_sat_history(sat)?,
         blocktime,
         inscriptions,
       }
@@ -623,7 +790,8 @@ impl Server {
     Extension(page_config): Extension<Arc<PageConfig>>,
     Extension(index): Extension<Arc<Index>>,
     Path(DeserializeFromStr(query)): Path<DeserializeFromStr<BlockQuery>>,
-  ) -> ServerResult<PageHtml<BlockHtml>> {
+    accept_json: AcceptJson,
+  ) -> ServerResult<Response> {
     let (block, height) = match query {
       BlockQuery::Height(height) => {
         let block = index
@@ -645,6 +813,20 @@ impl Server {
       }
     };
 
+    if accept_json.0 {
+      return Ok(
+        Json(BlockJson {
+          height,
+          hash: block.header.block_hash(),
+          timestamp: block.header.time,
+          transaction_count: block.txdata.len(),
+          ordinals_mined: Height(height).subsidy(),
+          inscriptions_created: index.get_inscriptions_in_block(height)?.len(),
+        })
+        .into_response(),
+      );
+    }
+
     let (featured_inscriptions, total_num) =
       index.get_highest_paying_inscriptions_in_block(height, 8)?;
 
@@ -653,10 +835,13 @@ impl Server {
         block,
         Height(height),
         Self::index_height(&index)?,
+        index.sat_range_for_height(Height(height))?,
         total_num,
         featured_inscriptions,
+        index.rare_sat_satpoints_in_block(Height(height))?,
       )
-      .page(page_config),
+      .page(page_config)
+      .into_response(),
     )
   }
 
@@ -665,7 +850,7 @@ impl Server {
     Extension(index): Extension<Arc<Index>>,
     Path(txid): Path<Txid>,
   ) -> ServerResult<PageHtml<TransactionHtml>> {
-    let inscription = index.get_inscription_by_id(InscriptionId { txid, index: 0 })?;
+    let inscriptions = index.inscriptions_in_transaction(txid)?;
 
     let blockhash = index.get_transaction_blockhash(txid)?;
 
@@ -675,13 +860,88 @@ impl Server {
           .get_transaction(txid)?
           .ok_or_not_found(|| format!("transaction {txid}"))?,
         blockhash,
-        inscription.map(|_| InscriptionId { txid, index: 0 }),
+        inscriptions,
         page_config.chain,
       )
       .page(page_config),
     )
   }
 
+  async fn metrics(Extension(index): Extension<Arc<Index>>) -> ServerResult<String> {
+    let mut metrics = vec![
+      (
+        "ord_blocks_indexed_total",
+        "counter",
+        "Blocks indexed.",
+        index.block_count()?,
+      ),
+      (
+        "ord_outputs_traversed_total",
+        "counter",
+        "Outputs traversed.",
+        index.get_statistic(Statistic::OutputsTraversed)?,
+      ),
+      (
+        "ord_sat_ranges_written_total",
+        "counter",
+        "Sat ranges written.",
+        index.get_statistic(Statistic::SatRanges)?,
+      ),
+      (
+        "ord_commits_total",
+        "counter",
+        "Index commits.",
+        index.get_statistic(Statistic::Commits)?,
+      ),
+      (
+        "ord_cache_hits_total",
+        "counter",
+        "Spent outputs found in the sat range cache.",
+        index.get_statistic(Statistic::OutputsCached)?,
+      ),
+      (
+        "ord_cache_misses_total",
+        "counter",
+        "Spent outputs read from the index file.",
+        index.get_statistic(Statistic::OutputsUncached)?,
+      ),
+    ];
+
+    if let Some(height) = index.block_height()? {
+      metrics.push((
+        "ord_current_height",
+        "gauge",
+        "Height of the last indexed block.",
+        height.n(),
+      ));
+    }
+
+    if let Some(blocks) = index.blocks_since_last_inscription()? {
+      metrics.push((
+        "ord_blocks_since_last_inscription",
+        "gauge",
+        "Blocks indexed since the last block containing an inscription.",
+        blocks,
+      ));
+    }
+
+    Ok(
+      metrics
+        .into_iter()
+        .map(|(name, kind, help, value)| {
+          format!("# HELP {name} {help}\n# TYPE {name} {kind}\n{name} {value}\n")
+        })
+        .collect(),
+    )
+  }
+
+  async fn health() -> (StatusCode, &'static str) {
+    (
+      StatusCode::OK,
+      StatusCode::OK.canonical_reason().unwrap_or_default(),
+    )
+  }
+
   async fn status(Extension(index): Extension<Arc<Index>>) -> (StatusCode, &'static str) {
     if index.is_unrecoverably_reorged() {
       (
@@ -721,6 +981,7 @@ impl Server {
       static ref OUTPOINT: Regex = Regex::new(r"^[[:xdigit:]]{64}:\d+$").unwrap();
       static ref RUNE: Regex = Regex::new(r"^[A-Z]+$").unwrap();
       static ref RUNE_ID: Regex = Regex::new(r"^[0-9]+/[0-9]+$").unwrap();
+      static ref SATPOINT: Regex = Regex::new(r"^[[:xdigit:]]{64}:\d+:\d+$").unwrap();
     }
 
     let query = query.trim();
@@ -733,6 +994,15 @@ impl Server {
       }
     } else if OUTPOINT.is_match(query) {
       Ok(Redirect::to(&format!("/output/{query}")))
+    } else if SATPOINT.is_match(query) {
+      let satpoint = query
+        .parse::<SatPoint>()
+        .map_err(|err| ServerError::BadRequest(err.to_string()))?;
+
+      match index.sat_at_satpoint(satpoint)? {
+        Some(sat) => Ok(Redirect::to(&format!("/sat/{sat}"))),
+        None => Ok(Redirect::to(&format!("/output/{}", satpoint.outpoint))),
+      }
     } else if INSCRIPTION_ID.is_match(query) {
       Ok(Redirect::to(&format!("/inscription/{query}")))
     } else if RUNE.is_match(query) {
@@ -778,6 +1048,54 @@ impl Server {
     }
   }
 
+  async fn block_events(
+    Extension(index): Extension<Arc<Index>>,
+  ) -> Sse<impl Stream<Item = Result<sse::Event, serde_json::Error>>> {
+    let receiver = index.subscribe_block_events();
+
+    let blocks = stream::unfold(receiver, |mut receiver| async move {
+      loop {
+        match receiver.recv().await {
+          Ok(event) => return Some((event, receiver)),
+          Err(RecvError::Lagged(skipped)) => {
+            log::warn!("block event subscriber lagged, skipped {skipped} events");
+          }
+          Err(RecvError::Closed) => return None,
+        }
+      }
+    });
+
+    Sse::new(blocks.map(|event: BlockEvent| sse::Event::default().json_data(event)))
+  }
+
+  async fn inscription_events(
+    Extension(index): Extension<Arc<Index>>,
+  ) -> ServerResult<Sse<impl Stream<Item = Result<sse::Event, serde_json::Error>>>> {
+    let receiver = index.subscribe_inscription_events();
+
+    let sync = InscriptionStreamEvent::Sync {
+      current_height: index.block_height()?.map(|height| height.n()),
+    };
+
+    let inscriptions = stream::unfold(receiver, |mut receiver| async move {
+      loop {
+        match receiver.recv().await {
+          Ok(event) => return Some((InscriptionStreamEvent::Inscription(event), receiver)),
+          Err(RecvError::Lagged(skipped)) => {
+            log::warn!("inscription event subscriber lagged, skipped {skipped} events");
+          }
+          Err(RecvError::Closed) => return None,
+        }
+      }
+    });
+
+    Ok(Sse::new(
+      stream::once(async { sync })
+        .chain(inscriptions)
+        .map(|event| sse::Event::default().json_data(event)),
+    ))
+  }
+
   async fn feed(
     Extension(page_config): Extension<Arc<PageConfig>>,
     Extension(index): Extension<Arc<Index>>,
@@ -960,6 +1278,59 @@ impl Server {
     Some((headers, inscription.into_body()?))
   }
 
+  async fn output_preview(
+    Extension(index): Extension<Arc<Index>>,
+    Extension(config): Extension<Arc<Config>>,
+    State(server_config): State<Arc<ServerConfig>>,
+    Path((txid, vout)): Path<(Txid, u32)>,
+  ) -> ServerResult<Response> {
+    let outpoint = OutPoint { txid, vout };
+
+    let inscription_id = index
+      .get_inscriptions_on_output(outpoint)?
+      .into_iter()
+      .next()
+      .ok_or_not_found(|| format!("inscription on output {outpoint}"))?;
+
+    if config.is_hidden(inscription_id) {
+      return Ok(PreviewUnknownHtml.into_response());
+    }
+
+    let inscription = index
+      .get_inscription_by_id(inscription_id)?
+      .ok_or_not_found(|| format!("inscription {inscription_id}"))?;
+
+    if let Some(max_content_preview_size) = server_config.max_content_preview_size {
+      let content_length = inscription.content_length().unwrap_or_default();
+      if content_length > max_content_preview_size {
+        return Err(ServerError::BadRequest(format!(
+          "inscription {inscription_id} content is {content_length} bytes, more than the maximum preview size of {max_content_preview_size} bytes"
+        )));
+      }
+    }
+
+    let displayable = inscription
+      .content_type()
+      .map(|content_type| content_type.starts_with("image/") || content_type.starts_with("text/"))
+      .unwrap_or_default();
+
+    let (mut headers, body) = Self::content_response(inscription)
+      .ok_or_not_found(|| format!("inscription {inscription_id} content"))?;
+
+    if !displayable {
+      headers.insert(
+        header::CONTENT_TYPE,
+        HeaderValue::from_static("application/octet-stream"),
+      );
+      headers.insert(
+        header::CONTENT_DISPOSITION,
+        HeaderValue::from_static("attachment"),
+      );
+    }
+
+    Ok((headers, body).into_response())
+  }
+
   async fn preview(
     Extension(index): Extension<Arc<Index>>,
     Extension(config): Extension<Arc<Config>>,
@@ -1139,11 +1510,51 @@ impl Server {
   async fn inscriptions(
     Extension(page_config): Extension<Arc<PageConfig>>,
     Extension(index): Extension<Arc<Index>>,
+    Query(pagination): Query<Pagination>,
     accept_json: AcceptJson,
   ) -> ServerResult<Response> {
+    if accept_json.0 && !pagination.is_empty() {
+      return Self::inscriptions_paginated(&index, pagination);
+    }
+
     Self::inscriptions_inner(page_config, index, None, 100, accept_json).await
   }
 
+  fn inscriptions_paginated(index: &Index, pagination: Pagination) -> ServerResult<Response> {
+    let page_size = pagination
+      .page_size
+      .unwrap_or(Pagination::DEFAULT_PAGE_SIZE);
+
+    if page_size == 0 || page_size > Pagination::MAX_PAGE_SIZE {
+      return Err(ServerError::BadRequest(format!(
+        "page size must be between 1 and {}",
+        Pagination::MAX_PAGE_SIZE
+      )));
+    }
+
+    let start = match pagination.cursor {
+      Some(cursor) => cursor,
+      None => pagination
+        .page
+        .unwrap_or_default()
+        .checked_mul(page_size.try_into().unwrap())
+        .ok_or_else(|| ServerError::BadRequest("page out of range".into()))?,
+    };
+
+    let (items, total, next_cursor) = index.get_inscriptions_paginated(start, page_size)?;
+
+    Ok(
+      Json(InscriptionsPageJson {
+        items,
+        total,
+        page: start / u64::try_from(page_size).unwrap(),
+        page_size,
+        next_cursor,
+      })
+      .into_response(),
+    )
+  }
+
   async fn inscriptions_in_block(
     Extension(page_config): Extension<Arc<PageConfig>>,
     Extension(index): Extension<Arc<Index>>,
@@ -1181,6 +1592,58 @@ impl Server {
     })
   }
 
+  async fn inscriptions_for_address(
+    Extension(page_config): Extension<Arc<PageConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path(DeserializeFromStr(address)): Path<DeserializeFromStr<Address<NetworkUnchecked>>>,
+    accept_json: AcceptJson,
+  ) -> ServerResult<Response> {
+    if !index.has_address_index() {
+      return Err(ServerError::NotFound(
+        "this server has no address index".into(),
+      ));
+    }
+
+    let address = address
+      .require_network(page_config.chain.network())
+      .map_err(|err| ServerError::BadRequest(err.to_string()))?;
+
+    let inscriptions = index.all_inscriptions_for_address(&address)?;
+
+    Ok(if accept_json.0 {
+      Json(InscriptionsJson::new(inscriptions, None, None, None, None)).into_response()
+    } else {
+      InscriptionsHtml {
+        inscriptions,
+        prev: None,
+        next: None,
+      }
+      .page(page_config)
+      .into_response()
+    })
+  }
+
+  async fn inscriptions_with_content_hash(
+    Extension(page_config): Extension<Arc<PageConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path(DeserializeFromStr(hash)): Path<DeserializeFromStr<sha256::Hash>>,
+    accept_json: AcceptJson,
+  ) -> ServerResult<Response> {
+    let inscriptions = index.inscriptions_with_content_hash(hash.to_byte_array())?;
+
+    Ok(if accept_json.0 {
+      Json(InscriptionsJson::new(inscriptions, None, None, None, None)).into_response()
+    } else {
+      InscriptionsHtml {
+        inscriptions,
+        prev: None,
+        next: None,
+      }
+      .page(page_config)
+      .into_response()
+    })
+  }
+
   async fn inscriptions_from(
     Extension(page_config): Extension<Arc<PageConfig>>,
     Extension(index): Extension<Arc<Index>>,
@@ -1541,6 +2004,34 @@ mod tests {
     );
   }
 
+  #[test]
+  fn tls_cert_sets_https_port_to_443() {
+    let (_, server) = parse_server_args("ord server --tls-cert cert.pem --tls-key key.pem");
+    assert_eq!(server.https_port(), Some(443));
+    assert_eq!(server.http_port(), None);
+  }
+
+  #[test]
+  fn tls_cert_requires_tls_key() {
+    assert!(Arguments::try_parse_from(["ord", "server", "--tls-cert", "cert.pem"]).is_err());
+    assert!(Arguments::try_parse_from(["ord", "server", "--tls-key", "key.pem"]).is_err());
+  }
+
+  #[test]
+  fn missing_tls_cert_is_an_error() {
+    let (options, server) = parse_server_args("ord server --tls-cert cert.pem --tls-key key.pem");
+
+    assert_eq!(
+      Runtime::new()
+        .unwrap()
+        .block_on(server.https_spawn_config(&options))
+        .err()
+        .unwrap()
+        .to_string(),
+      "failed to load TLS certificate `cert.pem` and key `key.pem`"
+    );
+  }
+
   #[test]
   fn https_disables_http() {
     assert_eq!(
@@ -1752,6 +2243,26 @@ mod tests {
     );
   }
 
+  #[test]
+  fn search_for_satpoint_returns_sat() {
+    let server = TestServer::new_with_regtest_with_index_sats();
+
+    let txid = server.mine_blocks(1)[0].txdata[0].txid();
+
+    server.assert_redirect(
+      &format!("/search/{txid}:0:1"),
+      &format!("/sat/{}", 50 * COIN_VALUE + 1),
+    );
+  }
+
+  #[test]
+  fn search_for_satpoint_without_sat_index_returns_output() {
+    TestServer::new().assert_redirect(
+      "/search/0000000000000000000000000000000000000000000000000000000000000000:0:1",
+      "/output/0000000000000000000000000000000000000000000000000000000000000000:0",
+    );
+  }
+
   #[test]
   fn search_for_inscription_id_returns_inscription() {
     TestServer::new().assert_redirect(
@@ -1986,6 +2497,174 @@ mod tests {
     );
   }
 
+  #[test]
+  fn block_with_sat_index_lists_rare_sats() {
+    TestServer::new_with_sat_index().assert_response_regex(
+      "/block/0",
+      StatusCode::OK,
+      ".*<h2>1 Rare Sat</h2>
+<ul class=monospace>
+  <li><a href=/sat/0 class=mythic>0</a> at <a href=/output/4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b:0>4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b:0:0</a></li>
+</ul>.*",
+    );
+  }
+
+  #[test]
+  fn metrics() {
+    let test_server = TestServer::new_with_regtest_with_index_sats();
+
+    test_server.mine_blocks(1);
+
+    test_server.assert_response_regex(
+      "/metrics",
+      StatusCode::OK,
+      "# HELP ord_blocks_indexed_total Blocks indexed.
+# TYPE ord_blocks_indexed_total counter
+ord_blocks_indexed_total 2
+# HELP ord_outputs_traversed_total Outputs traversed.
+# TYPE ord_outputs_traversed_total counter
+ord_outputs_traversed_total 2
+# HELP ord_sat_ranges_written_total Sat ranges written.
+# TYPE ord_sat_ranges_written_total counter
+ord_sat_ranges_written_total 2
+# HELP ord_commits_total Index commits.
+# TYPE ord_commits_total counter
+ord_commits_total \\d+
+.*
+# HELP ord_current_height Height of the last indexed block.
+# TYPE ord_current_height gauge
+ord_current_height 1
+",
+    );
+  }
+
+  #[test]
+  fn auth_token_is_required() {
+    let server = TestServer::new_with_args(&[], &["--auth-token", "foo"]);
+
+    let client = reqwest::blocking::Client::new();
+
+    assert_eq!(
+      client
+        .get(server.join_url("/blockcount"))
+        .send()
+        .unwrap()
+        .status(),
+      StatusCode::UNAUTHORIZED
+    );
+
+    assert_eq!(
+      client
+        .get(server.join_url("/blockcount"))
+        .bearer_auth("bar")
+        .send()
+        .unwrap()
+        .status(),
+      StatusCode::UNAUTHORIZED
+    );
+
+    assert_eq!(
+      client
+        .get(server.join_url("/blockcount"))
+        .bearer_auth("foo")
+        .send()
+        .unwrap()
+        .status(),
+      StatusCode::OK
+    );
+  }
+
+  #[test]
+  fn constant_time_eq() {
+    assert!(super::constant_time_eq(b"Bearer foo", b"Bearer foo"));
+    assert!(!super::constant_time_eq(b"Bearer foo", b"Bearer bar"));
+    assert!(!super::constant_time_eq(b"Bearer foo", b"Bearer fo"));
+    assert!(!super::constant_time_eq(b"", b"Bearer foo"));
+  }
+
+  #[test]
+  fn health_does_not_require_auth_token() {
+    TestServer::new_with_args(&[], &["--auth-token", "foo"]).assert_response(
+      "/health",
+      StatusCode::OK,
+      "OK",
+    );
+  }
+
+  #[test]
+  fn cors_allows_any_origin_by_default() {
+    let server = TestServer::new();
+
+    let response = reqwest::blocking::Client::new()
+      .get(server.join_url("/blockcount"))
+      .header(header::ORIGIN, "https://example.com")
+      .send()
+      .unwrap();
+
+    assert_eq!(
+      response
+        .headers()
+        .get(header::ACCESS_CONTROL_ALLOW_ORIGIN)
+        .unwrap(),
+      "*"
+    );
+  }
+
+  #[test]
+  fn cors_origin_and_max_age() {
+    let server = TestServer::new_with_args(
+      &[],
+      &[
+        "--cors-origin",
+        "https://example.com",
+        "--cors-max-age",
+        "600",
+      ],
+    );
+
+    let client = reqwest::blocking::Client::new();
+
+    let response = client
+      .get(server.join_url("/blockcount"))
+      .header(header::ORIGIN, "https://example.com")
+      .send()
+      .unwrap();
+
+    assert_eq!(
+      response
+        .headers()
+        .get(header::ACCESS_CONTROL_ALLOW_ORIGIN)
+        .unwrap(),
+      "https://example.com"
+    );
+
+    let response = client
+      .get(server.join_url("/blockcount"))
+      .header(header::ORIGIN, "https://example.org")
+      .send()
+      .unwrap();
+
+    assert_eq!(
+      response.headers().get(header::ACCESS_CONTROL_ALLOW_ORIGIN),
+      None
+    );
+
+    let response = client
+      .request(http::Method::OPTIONS, server.join_url("/blockcount"))
+      .header(header::ORIGIN, "https://example.com")
+      .header(header::ACCESS_CONTROL_REQUEST_METHOD, "GET")
+      .send()
+      .unwrap();
+
+    assert_eq!(
+      response
+        .headers()
+        .get(header::ACCESS_CONTROL_MAX_AGE)
+        .unwrap(),
+      "600"
+    );
+  }
+
   #[test]
   fn sat_out_of_range() {
     TestServer::new().assert_response(
@@ -2847,6 +3526,81 @@ mod tests {
     );
   }
 
+  #[test]
+  fn inscription_events() {
+    fn next_event(response: &mut reqwest::blocking::Response) -> String {
+      let mut event = Vec::new();
+      let mut byte = [0];
+      while !event.ends_with(b"\n\n") {
+        io::Read::read_exact(response, &mut byte).unwrap();
+        event.push(byte[0]);
+      }
+      String::from_utf8(event).unwrap()
+    }
+
+    let server = TestServer::new_with_regtest_with_index_sats();
+    server.mine_blocks(1);
+
+    let mut response = reqwest::blocking::get(server.join_url("/inscriptions/events")).unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+      response.headers().get(header::CONTENT_TYPE).unwrap(),
+      "text/event-stream"
+    );
+
+    pretty_assert_eq!(
+      next_event(&mut response),
+      "data:{\"type\":\"sync\",\"current_height\":1}\n\n"
+    );
+
+    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/foo", "hello").to_witness())],
+      ..Default::default()
+    });
+
+    server.mine_blocks(1);
+
+    pretty_assert_eq!(
+      next_event(&mut response),
+      format!(
+        "data:{{\"type\":\"inscription\",\"block_height\":2,\"content_type\":\"text/foo\",\"inscription_id\":\"{}\",\"sat\":{},\"txid\":\"{txid}\"}}\n\n",
+        InscriptionId { txid, index: 0 },
+        50 * COIN_VALUE,
+      )
+    );
+  }
+
+  #[test]
+  fn block_events() {
+    let server = TestServer::new_with_regtest();
+
+    let mut response = reqwest::blocking::get(server.join_url("/blocks/events")).unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+      response.headers().get(header::CONTENT_TYPE).unwrap(),
+      "text/event-stream"
+    );
+
+    let block_hash = server.mine_blocks(1)[0].block_hash();
+
+    let mut event = Vec::new();
+    let mut byte = [0];
+    while !event.ends_with(b"\n\n") {
+      io::Read::read_exact(&mut response, &mut byte).unwrap();
+      event.push(byte[0]);
+    }
+
+    pretty_assert_eq!(
+      String::from_utf8(event).unwrap(),
+      format!(
+        "data:{{\"block_hash\":\"{block_hash}\",\"height\":1,\"inscriptions_added\":0,\"ordinals_mined\":{}}}\n\n",
+        50 * COIN_VALUE,
+      )
+    );
+  }
+
   #[test]
   fn inscription_page_has_sat_when_sats_are_tracked() {
     let server = TestServer::new_with_regtest_with_index_sats();
@@ -2989,6 +3743,100 @@ mod tests {
     );
   }
 
+  #[test]
+  fn output_preview_serves_text_and_images_inline() {
+    let server = TestServer::new_with_regtest();
+    server.mine_blocks(1);
+
+    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      ..Default::default()
+    });
+
+    server.mine_blocks(1);
+
+    let response = server.get(format!("/output/{txid}/0/preview"));
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+      response.headers().get(header::CONTENT_TYPE).unwrap(),
+      "text/plain"
+    );
+    assert!(response
+      .headers()
+      .get(header::CONTENT_DISPOSITION)
+      .is_none());
+    assert_eq!(response.text().unwrap(), "hello");
+  }
+
+  #[test]
+  fn output_preview_serves_other_content_as_attachment() {
+    let server = TestServer::new_with_regtest();
+    server.mine_blocks(1);
+
+    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("application/json", "{}").to_witness())],
+      ..Default::default()
+    });
+
+    server.mine_blocks(1);
+
+    let response = server.get(format!("/output/{txid}/0/preview"));
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+      response.headers().get(header::CONTENT_TYPE).unwrap(),
+      "application/octet-stream"
+    );
+    assert_eq!(
+      response.headers().get(header::CONTENT_DISPOSITION).unwrap(),
+      "attachment"
+    );
+    assert_eq!(response.text().unwrap(), "{}");
+  }
+
+  #[test]
+  fn output_preview_not_found() {
+    let server = TestServer::new_with_regtest();
+    server.mine_blocks(1);
+
+    let txid = server.mine_blocks(1)[0].txdata[0].txid();
+
+    server.assert_response(
+      format!("/output/{txid}/0/preview"),
+      StatusCode::NOT_FOUND,
+      &format!("inscription on output {txid}:0 not found"),
+    );
+  }
+
+  #[test]
+  fn output_preview_respects_max_content_preview_size() {
+    let server = TestServer::new_server(
+      test_bitcoincore_rpc::builder()
+        .network(bitcoin::Network::Regtest)
+        .build(),
+      None,
+      &["--chain", "regtest"],
+      &["--max-content-preview-size", "4"],
+    );
+    server.mine_blocks(1);
+
+    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      ..Default::default()
+    });
+
+    server.mine_blocks(1);
+
+    server.assert_response(
+      format!("/output/{txid}/0/preview"),
+      StatusCode::BAD_REQUEST,
+      &format!(
+        "inscription {txid}i0 content is 5 bytes, more than the maximum preview size of 4 bytes"
+      ),
+    );
+  }
+
   #[test]
   fn inscriptions_page_with_no_prev_or_next() {
     TestServer::new_with_regtest_with_index_sats().assert_response_regex(
@@ -3019,6 +3867,50 @@ mod tests {
     );
   }
 
+  #[test]
+  fn inscriptions_for_address_requires_address_index() {
+    TestServer::new().assert_response(
+      "/inscriptions/address/bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4",
+      StatusCode::NOT_FOUND,
+      "this server has no address index",
+    );
+  }
+
+  #[test]
+  fn inscriptions_for_address_on_wrong_network() {
+    TestServer::new_with_args(&["--index-addresses"], &[]).assert_response(
+      "/inscriptions/address/tb1qw508d6qejxtdg4y5r3zarvary0c5xw7kxpjzsx",
+      StatusCode::BAD_REQUEST,
+      "address tb1qw508d6qejxtdg4y5r3zarvary0c5xw7kxpjzsx belongs to network testnet which is different from required bitcoin",
+    );
+  }
+
+  #[test]
+  fn inscriptions_with_content_hash() {
+    let server = TestServer::new_with_regtest();
+    server.mine_blocks(2);
+
+    let first = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      ..Default::default()
+    });
+    server.mine_blocks(1);
+
+    let second = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 0, 0, inscription("text/plain", "hello").to_witness())],
+      ..Default::default()
+    });
+    server.mine_blocks(1);
+
+    for txid in [first, second] {
+      server.assert_response_regex(
+        format!("/inscriptions/sha256/{}", sha256::Hash::hash(b"hello")),
+        StatusCode::OK,
+        format!(".*<a href=/inscription/{txid}i0>.*"),
+      );
+    }
+  }
+
   #[test]
   fn inscriptions_page_with_no_prev() {
     let server = TestServer::new_with_regtest_with_index_sats();
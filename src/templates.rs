@@ -1,13 +1,13 @@
 use {super::*, boilerplate::Boilerplate};
 
 pub(crate) use {
-  block::BlockHtml,
+  block::{BlockHtml, BlockJson},
   clock::ClockSvg,
   home::HomeHtml,
   iframe::Iframe,
   input::InputHtml,
   inscription::{InscriptionHtml, InscriptionJson},
-  inscriptions::{InscriptionsHtml, InscriptionsJson},
+  inscriptions::{InscriptionsHtml, InscriptionsJson, InscriptionsPageJson},
   inscriptions_block::InscriptionsBlockHtml,
   metadata::MetadataHtml,
   output::{OutputHtml, OutputJson},
@@ -24,7 +24,7 @@ pub(crate) use {
   transaction::TransactionHtml,
 };
 
-mod block;
+pub mod block;
 mod clock;
 mod home;
 mod iframe;
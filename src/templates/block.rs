@@ -7,8 +7,20 @@ pub(crate) struct BlockHtml {
   best_height: Height,
   block: Block,
   height: Height,
+  sat_range: Option<(u64, u64)>,
   total_num_inscriptions: usize,
   featured_inscriptions: Vec<InscriptionId>,
+  rare_sats: Vec<(Sat, SatPoint)>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct BlockJson {
+  pub height: u64,
+  pub hash: BlockHash,
+  pub timestamp: u32,
+  pub transaction_count: usize,
+  pub ordinals_mined: u64,
+  pub inscriptions_created: usize,
 }
 
 impl BlockHtml {
@@ -16,8 +28,10 @@ impl BlockHtml {
     block: Block,
     height: Height,
     best_height: Height,
+    sat_range: Option<(u64, u64)>,
     total_num_inscriptions: usize,
     featured_inscriptions: Vec<InscriptionId>,
+    rare_sats: Vec<(Sat, SatPoint)>,
   ) -> Self {
     Self {
       hash: block.header.block_hash(),
@@ -25,8 +39,10 @@ impl BlockHtml {
       block,
       height,
       best_height,
+      sat_range,
       total_num_inscriptions,
       featured_inscriptions,
+      rare_sats,
     }
   }
 }
@@ -48,7 +64,9 @@ mod tests {
         Chain::Mainnet.genesis_block(),
         Height(0),
         Height(0),
+        Some((0, 50 * COIN_VALUE)),
         0,
+        Vec::new(),
         Vec::new()
       ),
       "
@@ -59,6 +77,7 @@ mod tests {
           <dt>timestamp</dt><dd><time>2009-01-03 18:15:05 UTC</time></dd>
           <dt>size</dt><dd>285</dd>
           <dt>weight</dt><dd>1140</dd>
+          <dt>sats</dt><dd><a href=/range/0/5000000000>0–5000000000</a></dd>
         </dl>
         .*
         prev
@@ -83,7 +102,9 @@ mod tests {
         Chain::Mainnet.genesis_block(),
         Height(0),
         Height(1),
+        None,
         0,
+        Vec::new(),
         Vec::new()
       ),
       r"<h1>Block 0</h1>.*prev\s*<a class=next href=/block/1>next</a>.*"
@@ -97,7 +118,9 @@ mod tests {
         Chain::Mainnet.genesis_block(),
         Height(1),
         Height(1),
+        None,
         0,
+        Vec::new(),
         Vec::new()
       ),
       r"<h1>Block 1</h1>.*<a class=prev href=/block/0>prev</a>\s*next.*",
@@ -34,6 +34,15 @@ impl InscriptionsJson {
   }
 }
 
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct InscriptionsPageJson {
+  pub items: Vec<InscriptionId>,
+  pub total: u64,
+  pub page: u64,
+  pub page_size: usize,
+  pub next_cursor: Option<u64>,
+}
+
 impl PageContent for InscriptionsHtml {
   fn title(&self) -> String {
     "Inscriptions".into()
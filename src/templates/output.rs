@@ -120,6 +120,33 @@ mod tests {
     );
   }
 
+  #[test]
+  fn unconfirmed_output() {
+    assert_regex_match!(
+      OutputHtml {
+        inscriptions: Vec::new(),
+        outpoint: outpoint(1),
+        list: Some(List::Unconfirmed),
+        chain: Chain::Mainnet,
+        output: TxOut {
+          value: 1,
+          script_pubkey: script::Builder::new().push_int(0).into_script(),
+        },
+        runes: Vec::new(),
+      },
+      "
+        <h1>Output <span class=monospace>1{64}:1</span></h1>
+        <dl>
+          <dt>value</dt><dd>1</dd>
+          <dt>script pubkey</dt><dd class=monospace>OP_0</dd>
+          <dt>transaction</dt><dd><a class=monospace href=/tx/1{64}>1{64}</a></dd>
+        </dl>
+        <p>Output is unconfirmed.</p>
+      "
+      .unindent()
+    );
+  }
+
   #[test]
   fn no_list() {
     assert_regex_match!(
@@ -4,6 +4,7 @@ use super::*;
 pub(crate) struct SatHtml {
   pub(crate) sat: Sat,
   pub(crate) satpoint: Option<SatPoint>,
+  pub(crate) history: Option<Vec<SatPoint>>,
   pub(crate) blocktime: Blocktime,
   pub(crate) inscriptions: Vec<InscriptionId>,
 }
@@ -42,6 +43,7 @@ mod tests {
       SatHtml {
         sat: Sat(0),
         satpoint: None,
+        history: Some(Vec::new()),
         blocktime: Blocktime::confirmed(0),
         inscriptions: Vec::new(),
       },
@@ -75,6 +77,7 @@ mod tests {
       SatHtml {
         sat: Sat(2099999997689999),
         satpoint: None,
+        history: Some(Vec::new()),
         blocktime: Blocktime::confirmed(0),
         inscriptions: Vec::new(),
       },
@@ -108,6 +111,7 @@ mod tests {
       SatHtml {
         sat: Sat(1),
         satpoint: None,
+        history: Some(Vec::new()),
         blocktime: Blocktime::confirmed(0),
         inscriptions: Vec::new(),
       },
@@ -121,6 +125,7 @@ mod tests {
       SatHtml {
         sat: Sat(0),
         satpoint: None,
+        history: Some(Vec::new()),
         blocktime: Blocktime::confirmed(0),
         inscriptions: vec![inscription_id(1)],
       },
@@ -142,6 +147,7 @@ mod tests {
       SatHtml {
         sat: Sat(0),
         satpoint: None,
+        history: Some(Vec::new()),
         blocktime: Blocktime::confirmed(0),
         inscriptions: vec![inscription_id(1), inscription_id(2)],
       },
@@ -164,6 +170,7 @@ mod tests {
       SatHtml {
         sat: Sat::LAST,
         satpoint: None,
+        history: Some(Vec::new()),
         blocktime: Blocktime::confirmed(0),
         inscriptions: Vec::new(),
       },
@@ -177,10 +184,50 @@ mod tests {
       SatHtml {
         sat: Sat(0),
         satpoint: Some(satpoint(1, 0)),
+        history: Some(Vec::new()),
         blocktime: Blocktime::confirmed(0),
         inscriptions: Vec::new(),
       },
       "<h1>Sat 0</h1>.*<dt>location</dt><dd class=monospace>1{64}:1:0</dd>.*",
     );
   }
+
+  #[test]
+  fn sat_with_history() {
+    assert_regex_match!(
+      SatHtml {
+        sat: Sat(0),
+        satpoint: Some(satpoint(2, 0)),
+        history: Some(vec![satpoint(1, 0), satpoint(2, 0)]),
+        blocktime: Blocktime::confirmed(0),
+        inscriptions: Vec::new(),
+      },
+      "
+        <h1>Sat 0</h1>
+        .*
+          <dt>history</dt>
+          <dd>
+            <ul class=monospace>
+              <li>1{64}:1:0</li>
+              <li>2{64}:2:0</li>
+            </ul>
+          </dd>
+        .*"
+        .unindent(),
+    );
+  }
+
+  #[test]
+  fn common_sat_history_is_not_tracked() {
+    assert_regex_match!(
+      SatHtml {
+        sat: Sat(1),
+        satpoint: None,
+        history: None,
+        blocktime: Blocktime::confirmed(0),
+        inscriptions: Vec::new(),
+      },
+      "<h1>Sat 1</h1>.*<dt>history</dt><dd>not tracked for common sats</dd>.*",
+    );
+  }
 }
@@ -4,7 +4,7 @@ use super::*;
 pub(crate) struct TransactionHtml {
   blockhash: Option<BlockHash>,
   chain: Chain,
-  inscription: Option<InscriptionId>,
+  inscriptions: Vec<InscriptionId>,
   transaction: Transaction,
   txid: Txid,
 }
@@ -13,14 +13,14 @@ impl TransactionHtml {
   pub(crate) fn new(
     transaction: Transaction,
     blockhash: Option<BlockHash>,
-    inscription: Option<InscriptionId>,
+    inscriptions: Vec<InscriptionId>,
     chain: Chain,
   ) -> Self {
     Self {
       txid: transaction.txid(),
       blockhash,
       chain,
-      inscription,
+      inscriptions,
       transaction,
     }
   }
@@ -65,7 +65,7 @@ mod tests {
     let txid = transaction.txid();
 
     pretty_assert_eq!(
-      TransactionHtml::new(transaction, None, None, Chain::Mainnet).to_string(),
+      TransactionHtml::new(transaction, None, Vec::new(), Chain::Mainnet).to_string(),
       format!(
         "
         <h1>Transaction <span class=monospace>{txid}</span></h1>
@@ -119,7 +119,7 @@ mod tests {
     };
 
     assert_regex_match!(
-      TransactionHtml::new(transaction, Some(blockhash(0)), None, Chain::Mainnet),
+      TransactionHtml::new(transaction, Some(blockhash(0)), Vec::new(), Chain::Mainnet),
       "
         <h1>Transaction <span class=monospace>[[:xdigit:]]{64}</span></h1>
         <dl>
@@ -131,4 +131,33 @@ mod tests {
       .unindent()
     );
   }
+
+  #[test]
+  fn with_inscriptions() {
+    let transaction = Transaction {
+      version: 0,
+      lock_time: LockTime::ZERO,
+      input: Vec::new(),
+      output: Vec::new(),
+    };
+
+    assert_regex_match!(
+      TransactionHtml::new(
+        transaction,
+        None,
+        vec![inscription_id(1), inscription_id(2)],
+        Chain::Mainnet
+      ),
+      "
+        <h1>Transaction <span class=monospace>[[:xdigit:]]{64}</span></h1>
+        <h2>Inscription Geneses</h2>
+        <div class=thumbnails>
+          <a href=/inscription/1{64}i1><iframe .* src=/preview/1{64}i1></iframe></a>
+          <a href=/inscription/2{64}i2><iframe .* src=/preview/2{64}i2></iframe></a>
+        </div>
+        .*
+      "
+      .unindent()
+    );
+  }
 }
@@ -92,6 +92,9 @@ pub trait Api {
     blockhash: Option<BlockHash>,
   ) -> Result<Value, jsonrpc_core::Error>;
 
+  #[rpc(name = "getmempoolentry")]
+  fn get_mempool_entry(&self, txid: Txid) -> Result<GetMempoolEntryResult, jsonrpc_core::Error>;
+
   #[rpc(name = "listunspent")]
   fn list_unspent(
     &self,
@@ -102,6 +105,13 @@ pub trait Api {
     query_options: Option<String>,
   ) -> Result<Vec<ListUnspentResultEntry>, jsonrpc_core::Error>;
 
+  #[rpc(name = "scantxoutset")]
+  fn scan_tx_out_set(
+    &self,
+    action: String,
+    scan_objects: Vec<String>,
+  ) -> Result<ScanTxOutResult, jsonrpc_core::Error>;
+
   #[rpc(name = "listlockunspent")]
   fn list_lock_unspent(&self) -> Result<Vec<JsonOutPoint>, jsonrpc_core::Error>;
 
@@ -19,11 +19,12 @@ use {
   bitcoincore_rpc::json::{
     Bip125Replaceable, CreateRawTransactionInput, Descriptor, EstimateMode, GetBalancesResult,
     GetBalancesResultEntry, GetBlockHeaderResult, GetBlockchainInfoResult, GetDescriptorInfoResult,
-    GetNetworkInfoResult, GetRawTransactionResult, GetTransactionResult,
-    GetTransactionResultDetail, GetTransactionResultDetailCategory, GetWalletInfoResult,
-    ImportDescriptors, ImportMultiResult, ListDescriptorsResult, ListTransactionResult,
-    ListUnspentResultEntry, LoadWalletResult, SignRawTransactionInput, SignRawTransactionResult,
-    Timestamp, WalletTxInfo,
+    GetMempoolEntryResult, GetMempoolEntryResultFees, GetNetworkInfoResult,
+    GetRawTransactionResult, GetTransactionResult, GetTransactionResultDetail,
+    GetTransactionResultDetailCategory, GetWalletInfoResult, ImportDescriptors, ImportMultiResult,
+    ListDescriptorsResult, ListTransactionResult, ListUnspentResultEntry, LoadWalletResult,
+    ScanTxOutResult, SignRawTransactionInput, SignRawTransactionResult, Timestamp, Utxo,
+    WalletTxInfo,
   },
   jsonrpc_core::{IoHandler, Value},
   jsonrpc_http_server::{CloseHandle, ServerBuilder},
@@ -417,6 +417,38 @@ impl Api for Server {
     }
   }
 
+  fn get_mempool_entry(&self, txid: Txid) -> Result<GetMempoolEntryResult, jsonrpc_core::Error> {
+    let state = self.state();
+
+    let Some(tx) = state.mempool.iter().find(|tx| tx.txid() == txid) else {
+      return Err(jsonrpc_core::Error::new(
+        jsonrpc_core::types::error::ErrorCode::ServerError(-5),
+      ));
+    };
+
+    Ok(GetMempoolEntryResult {
+      vsize: tx.vsize().try_into().unwrap(),
+      weight: Some(tx.weight().to_wu()),
+      time: 0,
+      height: u64::try_from(state.hashes.len()).unwrap(),
+      descendant_count: 1,
+      descendant_size: tx.vsize().try_into().unwrap(),
+      ancestor_count: 1,
+      ancestor_size: tx.vsize().try_into().unwrap(),
+      wtxid: Txid::all_zeros(),
+      fees: GetMempoolEntryResultFees {
+        base: Amount::ZERO,
+        modified: Amount::ZERO,
+        ancestor: Amount::ZERO,
+        descendant: Amount::ZERO,
+      },
+      depends: Vec::new(),
+      spent_by: Vec::new(),
+      bip125_replaceable: false,
+      unbroadcast: None,
+    })
+  }
+
   fn list_unspent(
     &self,
     minconf: Option<usize>,
@@ -457,6 +489,53 @@ impl Api for Server {
     )
   }
 
+  fn scan_tx_out_set(
+    &self,
+    action: String,
+    scan_objects: Vec<String>,
+  ) -> Result<ScanTxOutResult, jsonrpc_core::Error> {
+    assert_eq!(action, "start", "only start action supported");
+
+    let state = self.state();
+
+    let mut unspents = Vec::new();
+
+    for descriptor in scan_objects {
+      let address = descriptor
+        .strip_prefix("addr(")
+        .and_then(|descriptor| descriptor.strip_suffix(')'))
+        .expect("only addr descriptors supported")
+        .parse::<Address<NetworkUnchecked>>()
+        .unwrap()
+        .assume_checked();
+
+      for (outpoint, &amount) in &state.utxos {
+        let script_pub_key =
+          &state.transactions[&outpoint.txid].output[outpoint.vout as usize].script_pubkey;
+
+        if *script_pub_key == address.script_pubkey() {
+          unspents.push(Utxo {
+            txid: outpoint.txid,
+            vout: outpoint.vout,
+            script_pub_key: script_pub_key.clone(),
+            descriptor: descriptor.clone(),
+            amount,
+            height: 0,
+          });
+        }
+      }
+    }
+
+    Ok(ScanTxOutResult {
+      success: Some(true),
+      tx_outs: Some(state.utxos.len().try_into().unwrap()),
+      height: Some((state.hashes.len() - 1).try_into().unwrap()),
+      best_block_hash: state.hashes.last().cloned(),
+      total_amount: unspents.iter().map(|utxo| utxo.amount).sum(),
+      unspents,
+    })
+  }
+
   fn list_lock_unspent(&self) -> Result<Vec<JsonOutPoint>, jsonrpc_core::Error> {
     Ok(
       self
@@ -0,0 +1,101 @@
+use {super::*, ord::subcommand::balance::Output};
+
+#[test]
+fn requires_sat_index() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+
+  CommandBuilder::new("balance bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4")
+    .rpc_server(&rpc_server)
+    .expected_exit_code(1)
+    .expected_stderr("error: balance requires index created with `--index-sats` flag\n")
+    .run_and_extract_stdout();
+}
+
+#[test]
+fn address_without_outputs() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+
+  let output =
+    CommandBuilder::new("--index-sats balance bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4")
+      .rpc_server(&rpc_server)
+      .run_and_deserialize_output::<Vec<Output>>();
+
+  assert!(output.is_empty());
+}
+
+#[test]
+fn address_with_outputs() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+
+  rpc_server.mine_blocks(1);
+
+  CommandBuilder::new(
+    "--index-sats wallet send --fee-rate 1 bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4 1btc",
+  )
+  .rpc_server(&rpc_server)
+  .run_and_deserialize_output::<ord::subcommand::wallet::send::Output>();
+
+  let txid = rpc_server.mempool()[0].txid();
+
+  rpc_server.mine_blocks(1);
+
+  let output =
+    CommandBuilder::new("--index-sats balance bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4")
+      .rpc_server(&rpc_server)
+      .run_and_deserialize_output::<Vec<Output>>();
+
+  assert_eq!(
+    output,
+    vec![
+      Output {
+        output: OutPoint { txid, vout: 0 },
+        value: COIN_VALUE,
+        ranges: vec![(50 * COIN_VALUE, 51 * COIN_VALUE)],
+        rare_sats: vec![ord::subcommand::balance::RareSat {
+          sat: ord::Sat(50 * COIN_VALUE),
+          offset: 0,
+          rarity: "uncommon".parse().unwrap(),
+        }],
+      },
+      Output {
+        output: OutPoint { txid, vout: 1 },
+        value: 49 * COIN_VALUE - 113,
+        ranges: vec![(51 * COIN_VALUE, 100 * COIN_VALUE - 113)],
+        rare_sats: Vec::new(),
+      },
+    ]
+  );
+}
+
+#[test]
+fn address_index_is_used_when_available() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+
+  rpc_server.mine_blocks(1);
+
+  CommandBuilder::new(
+    "--index-sats --index-addresses wallet send --fee-rate 1 bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4 1btc",
+  )
+  .rpc_server(&rpc_server)
+  .run_and_deserialize_output::<ord::subcommand::wallet::send::Output>();
+
+  let txid = rpc_server.mempool()[0].txid();
+
+  rpc_server.mine_blocks(1);
+
+  let output = CommandBuilder::new(
+    "--index-sats --index-addresses balance bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4",
+  )
+  .rpc_server(&rpc_server)
+  .run_and_deserialize_output::<Vec<Output>>();
+
+  assert_eq!(
+    output
+      .into_iter()
+      .map(|output| output.output)
+      .collect::<Vec<OutPoint>>(),
+    [OutPoint { txid, vout: 0 }, OutPoint { txid, vout: 1 }]
+  );
+}
@@ -2,12 +2,15 @@ use {
   super::*,
   bitcoin::{
     absolute::LockTime, consensus::Encodable, opcodes, script, ScriptBuf, Sequence, Transaction,
-    TxIn, Witness,
+    TxIn, TxOut, Witness,
+  },
+  ord::{
+    subcommand::decode::{Output, SatRanges},
+    Inscription,
   },
-  ord::{subcommand::decode::Output, Inscription},
 };
 
-fn transaction() -> Vec<u8> {
+fn transaction(output_values: &[u64]) -> Vec<u8> {
   let script = script::Builder::new()
     .push_opcode(opcodes::OP_FALSE)
     .push_opcode(opcodes::all::OP_IF)
@@ -33,7 +36,13 @@ fn transaction() -> Vec<u8> {
       sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
       witness,
     }],
-    output: Vec::new(),
+    output: output_values
+      .iter()
+      .map(|&value| TxOut {
+        value,
+        script_pubkey: ScriptBuf::new(),
+      })
+      .collect(),
   };
 
   let mut buffer = Vec::new();
@@ -47,7 +56,7 @@ fn transaction() -> Vec<u8> {
 fn from_file() {
   assert_eq!(
     CommandBuilder::new("decode transaction.bin")
-      .write("transaction.bin", transaction())
+      .write("transaction.bin", transaction(&[]))
       .run_and_deserialize_output::<Output>(),
     Output {
       inscriptions: vec![Inscription {
@@ -55,6 +64,7 @@ fn from_file() {
         content_type: Some(b"text/plain;charset=utf-8".to_vec()),
         ..Default::default()
       }],
+      sat_ranges: None,
     }
   );
 }
@@ -63,7 +73,7 @@ fn from_file() {
 fn from_stdin() {
   assert_eq!(
     CommandBuilder::new("decode")
-      .stdin(transaction())
+      .stdin(transaction(&[]))
       .run_and_deserialize_output::<Output>(),
     Output {
       inscriptions: vec![Inscription {
@@ -71,6 +81,57 @@ fn from_stdin() {
         content_type: Some(b"text/plain;charset=utf-8".to_vec()),
         ..Default::default()
       }],
+      sat_ranges: None,
     }
   );
 }
+
+#[test]
+fn sat_ranges_are_omitted_without_input_ranges() {
+  assert!(!CommandBuilder::new("decode")
+    .stdin(transaction(&[]))
+    .stdout_regex(".*")
+    .run_and_extract_stdout()
+    .contains("sat_ranges"));
+}
+
+#[test]
+fn with_input_ranges() {
+  assert_eq!(
+    CommandBuilder::new("decode --input-ranges [[[0,10],[20,30]]]")
+      .stdin(transaction(&[5, 10]))
+      .run_and_deserialize_output::<Output>()
+      .sat_ranges,
+    Some(SatRanges {
+      outputs: vec![vec![(0, 5)], vec![(5, 10), (20, 25)]],
+      fee: vec![(25, 30)],
+    })
+  );
+}
+
+#[test]
+fn input_ranges_must_match_inputs() {
+  CommandBuilder::new("decode --input-ranges [[[0,10]],[[20,30]]]")
+    .stdin(transaction(&[5]))
+    .expected_exit_code(1)
+    .expected_stderr("error: transaction has 1 inputs but ranges were given for 2\n")
+    .run_and_extract_stdout();
+}
+
+#[test]
+fn input_ranges_must_cover_outputs() {
+  CommandBuilder::new("decode --input-ranges [[[0,10]]]")
+    .stdin(transaction(&[20]))
+    .expected_exit_code(1)
+    .expected_stderr("error: insufficient inputs for transaction outputs\n")
+    .run_and_extract_stdout();
+}
+
+#[test]
+fn input_ranges_must_not_be_reversed() {
+  CommandBuilder::new("decode --input-ranges [[[10,0]]]")
+    .stdin(transaction(&[5]))
+    .expected_exit_code(1)
+    .expected_stderr("error: invalid input range 10-0\n")
+    .run_and_extract_stdout();
+}
@@ -13,7 +13,8 @@ fn find_command_returns_satpoint_for_sat() {
     Output {
       satpoint: "4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b:0:0"
         .parse()
-        .unwrap()
+        .unwrap(),
+      value: Some(50 * COIN_VALUE),
     }
   );
 }
@@ -81,3 +82,29 @@ fn no_satoshi_index() {
     .expected_exit_code(1)
     .run_and_extract_stdout();
 }
+
+#[test]
+fn find_command_returns_value_of_output_holding_sat() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+
+  rpc_server.mine_blocks(1);
+
+  let txid = rpc_server.broadcast_tx(TransactionTemplate {
+    inputs: &[(1, 0, 0, Default::default())],
+    outputs: 2,
+    fee: 0,
+    ..Default::default()
+  });
+
+  rpc_server.mine_blocks(1);
+
+  assert_eq!(
+    CommandBuilder::new(format!("--index-sats find {}", 75 * COIN_VALUE))
+      .rpc_server(&rpc_server)
+      .run_and_deserialize_output::<Output>(),
+    Output {
+      satpoint: format!("{txid}:1:0").parse().unwrap(),
+      value: Some(25 * COIN_VALUE),
+    }
+  );
+}
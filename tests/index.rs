@@ -32,6 +32,61 @@ fn custom_index_path() {
   assert!(index_path.is_file())
 }
 
+#[test]
+fn update_with_compact() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  rpc_server.mine_blocks(9);
+
+  let tempdir = TempDir::new().unwrap();
+
+  let index_path = tempdir.path().join("foo.redb");
+
+  CommandBuilder::new(format!(
+    "--index {} index update --compact",
+    index_path.display()
+  ))
+  .rpc_server(&rpc_server)
+  .run_and_deserialize_output::<Empty>();
+
+  assert!(index_path.is_file());
+
+  CommandBuilder::new(format!("--index {} index update", index_path.display()))
+    .rpc_server(&rpc_server)
+    .run_and_deserialize_output::<Empty>();
+}
+
+#[test]
+fn height_limit_below_index_height_is_an_error() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  rpc_server.mine_blocks(3);
+
+  let tempdir = TempDir::new().unwrap();
+
+  let index_path = tempdir.path().join("foo.redb");
+
+  CommandBuilder::new(format!("--index {} index update", index_path.display()))
+    .rpc_server(&rpc_server)
+    .run_and_deserialize_output::<Empty>();
+
+  CommandBuilder::new(format!(
+    "--index {} --height-limit 1 index update",
+    index_path.display()
+  ))
+  .rpc_server(&rpc_server)
+  .expected_exit_code(1)
+  .expected_stderr(
+    "error: height limit 1 is below index block count, index already contains 4 blocks\n",
+  )
+  .run_and_extract_stdout();
+
+  CommandBuilder::new(format!(
+    "--index {} --height-limit 4 index update",
+    index_path.display()
+  ))
+  .rpc_server(&rpc_server)
+  .run_and_deserialize_output::<Empty>();
+}
+
 #[test]
 fn re_opening_database_does_not_trigger_schema_check() {
   let rpc_server = test_bitcoincore_rpc::spawn();
@@ -116,3 +171,240 @@ fn export_inscription_number_to_id_tsv() {
     &ord::Object::InscriptionId(inscription),
   );
 }
+
+#[test]
+fn export_sats_csv() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  let coinbase = rpc_server.mine_blocks(1)[0].txdata[0].txid();
+
+  let csv = CommandBuilder::new("--index-sats index export-sats --output foo.csv")
+    .rpc_server(&rpc_server)
+    .stdout_regex(r"\{\}\n")
+    .run_and_extract_file("foo.csv");
+
+  let mut lines = csv.lines().collect::<Vec<&str>>();
+
+  assert_eq!(lines.remove(0), "txid,vout,ranges");
+
+  lines.sort();
+
+  let mut expected = vec![
+    format!("{},0,0-5000000000", rpc_server.tx(0, 0).txid()),
+    format!("{coinbase},0,5000000000-10000000000"),
+  ];
+
+  expected.sort();
+
+  assert_eq!(lines, expected);
+}
+
+#[test]
+fn export_sats_json() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  let coinbase = rpc_server.mine_blocks(1)[0].txdata[0].txid();
+
+  let json = CommandBuilder::new("--index-sats index export-sats --format json")
+    .rpc_server(&rpc_server)
+    .stdout_regex(r"\[.*\]\n")
+    .run_and_extract_stdout();
+
+  let rows = serde_json::from_str::<Vec<serde_json::Value>>(&json).unwrap();
+
+  assert_eq!(rows.len(), 2);
+
+  assert!(rows.contains(&serde_json::json!({
+    "txid": coinbase,
+    "vout": 0,
+    "ranges": [[5000000000u64, 10000000000u64]],
+  })));
+}
+
+#[test]
+fn export_sats_requires_sat_index() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+
+  CommandBuilder::new("index export-sats")
+    .rpc_server(&rpc_server)
+    .expected_exit_code(1)
+    .expected_stderr("error: export-sats requires index created with `--index-sats` flag\n")
+    .run_and_extract_stdout();
+}
+
+#[test]
+fn verify_succeeds_on_intact_index() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  rpc_server.mine_blocks(3);
+
+  let tempdir = TempDir::new().unwrap();
+
+  let index_path = tempdir.path().join("foo.redb");
+
+  CommandBuilder::new(format!("--index {} index update", index_path.display()))
+    .rpc_server(&rpc_server)
+    .run_and_deserialize_output::<Empty>();
+
+  CommandBuilder::new(format!("--index {} index verify", index_path.display()))
+    .rpc_server(&rpc_server)
+    .run_and_deserialize_output::<Empty>();
+}
+
+#[test]
+fn verify_inscription_matches_index() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+
+  let Inscribe { inscription, .. } = inscribe(&rpc_server);
+
+  let tempdir = TempDir::new().unwrap();
+
+  let index_path = tempdir.path().join("foo.redb");
+
+  CommandBuilder::new(format!("--index {} index update", index_path.display()))
+    .rpc_server(&rpc_server)
+    .run_and_deserialize_output::<Empty>();
+
+  let output = CommandBuilder::new(format!(
+    "--index {} index verify-inscription {inscription}",
+    index_path.display()
+  ))
+  .rpc_server(&rpc_server)
+  .run_and_deserialize_output::<serde_json::Value>();
+
+  assert_eq!(
+    output,
+    serde_json::json!({
+      "inscription": inscription,
+      "height": 2,
+      "content_type": "text/plain;charset=utf-8",
+      "content_length": 3,
+      "content_sha256": "9520437ce8902eb379a7d8aaa98fc4c94eeb07b6684854868fa6f72bf34b0fd3",
+      "satpoint": format!("{}:0:0", inscription.txid),
+      "fee": 138,
+      "timestamp": 2,
+    })
+  );
+}
+
+#[test]
+fn verify_inscription_fails_on_unknown_inscription() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  rpc_server.mine_blocks(1);
+
+  CommandBuilder::new(
+    "index verify-inscription 0000000000000000000000000000000000000000000000000000000000000000i0",
+  )
+  .rpc_server(&rpc_server)
+  .expected_exit_code(1)
+  .expected_stderr(
+    "error: inscription 0000000000000000000000000000000000000000000000000000000000000000i0 not found in index\n",
+  )
+  .run_and_extract_stdout();
+}
+
+#[test]
+fn dry_run_reports_blocks_behind() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  rpc_server.mine_blocks(1);
+
+  let tempdir = TempDir::new().unwrap();
+
+  let index_path = tempdir.path().join("foo.redb");
+
+  CommandBuilder::new(format!("--index {} index update", index_path.display()))
+    .rpc_server(&rpc_server)
+    .run_and_deserialize_output::<Empty>();
+
+  CommandBuilder::new(format!(
+    "--index {} index update --dry-run",
+    index_path.display()
+  ))
+  .rpc_server(&rpc_server)
+  .stdout_regex(r#"\{\s*"blocks_behind": 0\s*\}\n"#)
+  .run_and_extract_stdout();
+
+  rpc_server.mine_blocks(3);
+
+  CommandBuilder::new(format!(
+    "--index {} index update --dry-run",
+    index_path.display()
+  ))
+  .rpc_server(&rpc_server)
+  .expected_exit_code(1)
+  .expected_stderr("error: index is 3 blocks behind\n")
+  .run_and_extract_stdout();
+
+  CommandBuilder::new(format!("--index {} index update", index_path.display()))
+    .rpc_server(&rpc_server)
+    .run_and_deserialize_output::<Empty>();
+
+  rpc_server.mine_blocks(2);
+
+  CommandBuilder::new(format!(
+    "--index {} index update --dry-run",
+    index_path.display()
+  ))
+  .rpc_server(&rpc_server)
+  .expected_exit_code(1)
+  .stderr_regex(
+    r"error: index is 2 blocks behind, about \d+s at the most recent rate of \d+\.\d\d blocks/s\n",
+  )
+  .run_and_extract_stdout();
+}
+
+#[test]
+fn resume_fails_if_recent_blocks_were_reorged() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  rpc_server.mine_blocks(3);
+
+  let tempdir = TempDir::new().unwrap();
+
+  let index_path = tempdir.path().join("foo.redb");
+
+  CommandBuilder::new(format!("--index {} index update", index_path.display()))
+    .rpc_server(&rpc_server)
+    .run_and_deserialize_output::<Empty>();
+
+  rpc_server.mine_blocks(1);
+
+  CommandBuilder::new(format!(
+    "--index {} index update --resume",
+    index_path.display()
+  ))
+  .rpc_server(&rpc_server)
+  .run_and_deserialize_output::<Empty>();
+
+  rpc_server.invalidate_tip();
+  rpc_server.mine_blocks(2);
+
+  CommandBuilder::new(format!(
+    "--index {} index update --resume",
+    index_path.display()
+  ))
+  .rpc_server(&rpc_server)
+  .expected_exit_code(1)
+  .stderr_regex(
+    "error: indexed block 4 [[:xdigit:]]{64} does not match bitcoind's [[:xdigit:]]{64}, chain reorganized since index was last updated\n",
+  )
+  .run_and_extract_stdout();
+}
+
+#[test]
+fn update_fails_when_index_exceeds_max_index_size() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  rpc_server.mine_blocks(1);
+
+  let tempdir = TempDir::new().unwrap();
+
+  let index_path = tempdir.path().join("foo.redb");
+
+  CommandBuilder::new(format!(
+    "--index {} --max-index-size 1 index update",
+    index_path.display()
+  ))
+  .rpc_server(&rpc_server)
+  .expected_exit_code(1)
+  .stderr_regex(
+    "error: index file `.*foo.redb` is [0-9]+ bytes, which exceeds the maximum index size of 1 bytes\n",
+  )
+  .run_and_extract_stdout();
+}
@@ -9,14 +9,25 @@ fn json_with_satoshi_index() {
       r#"\{
   "blocks_indexed": 1,
   "branch_pages": \d+,
+  "chain_blocks": 1,
+  "commits": \d+,
   "fragmented_bytes": \d+,
+  "index_addresses": false,
   "index_file_size": \d+,
   "index_path": ".*\.redb",
+  "index_runes": false,
+  "index_sats": true,
+  "index_spent_outputs": false,
+  "index_txids": false,
+  "inscriptions": 0,
   "leaf_pages": \d+,
   "metadata_bytes": \d+,
+  "outputs_cached": 0,
   "outputs_traversed": 1,
+  "outputs_uncached": 0,
   "page_size": \d+,
   "sat_ranges": 1,
+  "sats_indexed": 5000000000,
   "stored_bytes": \d+,
   "transactions": \[
     \{
@@ -41,14 +52,25 @@ fn json_without_satoshi_index() {
       r#"\{
   "blocks_indexed": 1,
   "branch_pages": \d+,
+  "chain_blocks": 1,
+  "commits": \d+,
   "fragmented_bytes": \d+,
+  "index_addresses": false,
   "index_file_size": \d+,
   "index_path": ".*\.redb",
+  "index_runes": false,
+  "index_sats": false,
+  "index_spent_outputs": false,
+  "index_txids": false,
+  "inscriptions": 0,
   "leaf_pages": \d+,
   "metadata_bytes": \d+,
+  "outputs_cached": 0,
   "outputs_traversed": 0,
+  "outputs_uncached": 0,
   "page_size": \d+,
   "sat_ranges": 0,
+  "sats_indexed": 5000000000,
   "stored_bytes": \d+,
   "transactions": \[
     \{
@@ -0,0 +1,74 @@
+use {super::*, ord::subcommand::inscriptions::Output};
+
+fn list_inscriptions(rpc_server: &test_bitcoincore_rpc::Handle, args: &str) -> Vec<Output> {
+  CommandBuilder::new(format!("inscriptions {args}"))
+    .rpc_server(rpc_server)
+    .stdout_regex(".*")
+    .run_and_extract_stdout()
+    .lines()
+    .map(|line| serde_json::from_str(line).unwrap())
+    .collect()
+}
+
+#[test]
+fn inscriptions_in_height_range() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+
+  let Inscribe { inscription, .. } = inscribe(&rpc_server);
+
+  let output = list_inscriptions(&rpc_server, "--from-height 0 --to-height 3");
+
+  assert_eq!(
+    output,
+    [Output {
+      inscription,
+      number: 0,
+      height: 2,
+      sat: None,
+      content_type: Some("text/plain;charset=utf-8".into()),
+      content_length: Some(3),
+    }]
+  );
+
+  assert_eq!(
+    list_inscriptions(&rpc_server, "--from-height 0 --to-height 2"),
+    []
+  );
+}
+
+#[test]
+fn inscriptions_filtered_by_content_type() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+
+  inscribe(&rpc_server);
+
+  assert_eq!(
+    list_inscriptions(
+      &rpc_server,
+      "--from-height 0 --to-height 3 --content-type text/"
+    )
+    .len(),
+    1
+  );
+
+  assert_eq!(
+    list_inscriptions(
+      &rpc_server,
+      "--from-height 0 --to-height 3 --content-type image/"
+    ),
+    []
+  );
+}
+
+#[test]
+fn to_height_must_not_be_below_from_height() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+
+  CommandBuilder::new("inscriptions --from-height 2 --to-height 1")
+    .rpc_server(&rpc_server)
+    .expected_exit_code(1)
+    .expected_stderr("error: to height is below from height\n")
+    .run_and_extract_stdout();
+}
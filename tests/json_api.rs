@@ -127,6 +127,39 @@ fn get_sat_with_inscription_on_common_sat_and_more_inscriptions() {
   )
 }
 
+#[test]
+fn get_block() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+
+  create_wallet(&rpc_server);
+
+  rpc_server.mine_blocks(1);
+
+  CommandBuilder::new("wallet inscribe --fee-rate 1 foo.txt")
+    .write("foo.txt", "FOO")
+    .rpc_server(&rpc_server)
+    .run_and_deserialize_output::<Inscribe>();
+
+  let block = rpc_server.mine_blocks(1)[0].clone();
+
+  let response =
+    TestServer::spawn_with_args(&rpc_server, &["--enable-json-api"]).json_request("/block/2");
+
+  assert_eq!(response.status(), StatusCode::OK);
+
+  pretty_assert_eq!(
+    serde_json::from_str::<BlockJson>(&response.text().unwrap()).unwrap(),
+    BlockJson {
+      height: 2,
+      hash: block.block_hash(),
+      timestamp: block.header.time,
+      transaction_count: block.txdata.len(),
+      ordinals_mined: 50 * COIN_VALUE,
+      inscriptions_created: 1,
+    }
+  )
+}
+
 #[test]
 fn get_inscription() {
   let rpc_server = test_bitcoincore_rpc::spawn();
@@ -280,6 +313,83 @@ fn get_inscriptions() {
   }
 }
 
+#[test]
+fn get_inscriptions_paginated() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+
+  create_wallet(&rpc_server);
+  rpc_server.mine_blocks(10);
+
+  let envelope = envelope(&[b"ord", &[1], b"text/plain;charset=utf-8", &[], b"bar"]);
+
+  let txid = rpc_server.broadcast_tx(TransactionTemplate {
+    inputs: &[
+      (1, 0, 0, envelope.clone()),
+      (2, 0, 0, envelope.clone()),
+      (3, 0, 0, envelope.clone()),
+    ],
+    ..Default::default()
+  });
+
+  rpc_server.mine_blocks(1);
+
+  let server = TestServer::spawn_with_args(
+    &rpc_server,
+    &["--enable-json-api", "--first-inscription-height", "0"],
+  );
+
+  let response = server.json_request("/inscriptions?page_size=2");
+  assert_eq!(response.status(), StatusCode::OK);
+
+  pretty_assert_eq!(
+    serde_json::from_str::<InscriptionsPageJson>(&response.text().unwrap()).unwrap(),
+    InscriptionsPageJson {
+      items: vec![
+        InscriptionId { txid, index: 0 },
+        InscriptionId { txid, index: 1 },
+      ],
+      total: 3,
+      page: 0,
+      page_size: 2,
+      next_cursor: Some(2),
+    }
+  );
+
+  let response = server.json_request("/inscriptions?page=1&page_size=2");
+  assert_eq!(response.status(), StatusCode::OK);
+
+  pretty_assert_eq!(
+    serde_json::from_str::<InscriptionsPageJson>(&response.text().unwrap()).unwrap(),
+    InscriptionsPageJson {
+      items: vec![InscriptionId { txid, index: 2 }],
+      total: 3,
+      page: 1,
+      page_size: 2,
+      next_cursor: None,
+    }
+  );
+
+  let response = server.json_request("/inscriptions?cursor=1");
+  assert_eq!(response.status(), StatusCode::OK);
+
+  pretty_assert_eq!(
+    serde_json::from_str::<InscriptionsPageJson>(&response.text().unwrap())
+      .unwrap()
+      .items,
+    vec![
+      InscriptionId { txid, index: 1 },
+      InscriptionId { txid, index: 2 },
+    ]
+  );
+
+  assert_eq!(
+    server
+      .json_request("/inscriptions?page_size=10001")
+      .status(),
+    StatusCode::BAD_REQUEST
+  );
+}
+
 #[test]
 fn get_inscriptions_in_block() {
   let rpc_server = test_bitcoincore_rpc::spawn();
@@ -12,7 +12,10 @@ use {
     inscription_id::InscriptionId,
     rarity::Rarity,
     templates::{
-      inscription::InscriptionJson, inscriptions::InscriptionsJson, output::OutputJson,
+      block::BlockJson,
+      inscription::InscriptionJson,
+      inscriptions::{InscriptionsJson, InscriptionsPageJson},
+      output::OutputJson,
       sat::SatJson,
     },
     SatPoint,
@@ -93,15 +96,18 @@ mod command_builder;
 mod expected;
 mod test_server;
 
+mod balance;
 mod core;
 mod decode;
 mod epochs;
 mod find;
 mod index;
 mod info;
+mod inscriptions;
 mod json_api;
 mod list;
 mod parse;
+mod sat;
 mod server;
 mod subsidy;
 mod supply;
@@ -46,3 +46,79 @@ fn no_satoshi_index() {
     .expected_exit_code(1)
     .run_and_extract_stdout();
 }
+
+#[test]
+fn output_spent() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+
+  let txid = rpc_server.mine_blocks(1)[0].txdata[0].txid();
+
+  rpc_server.broadcast_tx(TransactionTemplate {
+    inputs: &[(1, 0, 0, Default::default())],
+    ..Default::default()
+  });
+
+  rpc_server.mine_blocks(1);
+
+  CommandBuilder::new(format!("--index-sats --index-spent-outputs list {txid}:0"))
+    .rpc_server(&rpc_server)
+    .expected_exit_code(1)
+    .expected_stderr("error: output spent in block 2.\n")
+    .run_and_extract_stdout();
+}
+
+#[test]
+fn output_unconfirmed() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+
+  rpc_server.mine_blocks(1);
+
+  let txid = rpc_server.broadcast_tx(TransactionTemplate {
+    inputs: &[(1, 0, 0, Default::default())],
+    ..Default::default()
+  });
+
+  CommandBuilder::new(format!("--index-sats list {txid}:0"))
+    .rpc_server(&rpc_server)
+    .expected_exit_code(1)
+    .expected_stderr("error: output not yet confirmed\n")
+    .run_and_extract_stdout();
+}
+
+#[test]
+fn output_unconfirmed_with_txid_index() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+
+  rpc_server.mine_blocks(1);
+
+  let txid = rpc_server.broadcast_tx(TransactionTemplate {
+    inputs: &[(1, 0, 0, Default::default())],
+    ..Default::default()
+  });
+
+  CommandBuilder::new(format!("--index-sats --index-txids list {txid}:0"))
+    .rpc_server(&rpc_server)
+    .expected_exit_code(1)
+    .expected_stderr("error: output not yet confirmed\n")
+    .run_and_extract_stdout();
+}
+
+#[test]
+fn output_spent_with_txid_index() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+
+  let txid = rpc_server.mine_blocks(1)[0].txdata[0].txid();
+
+  rpc_server.broadcast_tx(TransactionTemplate {
+    inputs: &[(1, 0, 0, Default::default())],
+    ..Default::default()
+  });
+
+  rpc_server.mine_blocks(1);
+
+  CommandBuilder::new(format!("--index-sats --index-txids list {txid}:0"))
+    .rpc_server(&rpc_server)
+    .expected_exit_code(1)
+    .expected_stderr("error: output spent.\n")
+    .run_and_extract_stdout();
+}
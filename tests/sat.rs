@@ -0,0 +1,47 @@
+use {super::*, ord::subcommand::sat::Output, ord::Rarity};
+
+#[test]
+fn sat_command_prints_location_with_sat_index() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  let coinbase = rpc_server.mine_blocks(1)[0].txdata[0].txid();
+
+  assert_eq!(
+    CommandBuilder::new("--index-sats sat 5000000001")
+      .rpc_server(&rpc_server)
+      .run_and_deserialize_output::<Output>(),
+    Output {
+      number: 5000000001,
+      name: "nvtcsezkbtg".into(),
+      rarity: Rarity::Common,
+      epoch: 0,
+      block: 1,
+      offset: 1,
+      satpoint: Some(format!("{coinbase}:0:1").parse().unwrap()),
+      inscriptions: Vec::new(),
+      transfers: None,
+    }
+  );
+}
+
+#[test]
+fn sat_command_without_sat_index_has_no_location() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  rpc_server.mine_blocks(1);
+
+  assert_eq!(
+    CommandBuilder::new("sat 5000000000")
+      .rpc_server(&rpc_server)
+      .run_and_deserialize_output::<Output>(),
+    Output {
+      number: 5000000000,
+      name: "nvtcsezkbth".into(),
+      rarity: Rarity::Uncommon,
+      epoch: 0,
+      block: 1,
+      offset: 0,
+      satpoint: None,
+      inscriptions: Vec::new(),
+      transfers: Some(Vec::new()),
+    }
+  );
+}
@@ -214,6 +214,37 @@ fn inscriptions_page() {
   );
 }
 
+#[test]
+fn inscriptions_for_address() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+  rpc_server.mine_blocks(1);
+
+  let address = "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4";
+
+  let Inscribe { inscription, .. } = CommandBuilder::new(format!(
+    "wallet inscribe --fee-rate 1 --destination {address} foo.txt"
+  ))
+  .write("foo.txt", "FOO")
+  .rpc_server(&rpc_server)
+  .run_and_deserialize_output();
+
+  rpc_server.mine_blocks(1);
+
+  inscribe(&rpc_server);
+
+  TestServer::spawn_with_args(&rpc_server, &["--index-addresses"]).assert_response_regex(
+    format!("/inscriptions/address/{address}"),
+    format!(
+      ".*<h1>Inscriptions</h1>
+<div class=thumbnails>
+  <a href=/inscription/{inscription}>.*</a>
+</div>
+.*",
+    ),
+  );
+}
+
 #[test]
 fn inscriptions_page_is_sorted() {
   let rpc_server = test_bitcoincore_rpc::spawn();